@@ -1,7 +1,14 @@
+use all_of_hashtable::chaining::{
+    ChainingHashTable, LinkedListChain, MoveToFrontChain, SmallVecChain,
+};
+use all_of_hashtable::cuckoo::CuckooHashTable;
+use all_of_hashtable::hopscotch::HopscotchHashTable;
 use all_of_hashtable::open_addressing::{
-    FcfsDoubleHashing, FcfsLinearProbing, FcfsQuadraticProbing, LcfsLinearProbing,
-    OpenAddressingHashTable,
+    FcfsDoubleHashing, FcfsLinearProbing, FcfsQuadraticProbing, FcfsRandomProbing,
+    LcfsDoubleHashing, LcfsLinearProbing, LcfsQuadraticProbing, LcfsRandomProbing,
+    OpenAddressingHashTable, RobinHoodLinearProbing,
 };
+use all_of_hashtable::swiss::SwissTable;
 use criterion::{criterion_group, criterion_main, Criterion, SamplingMode, Throughput};
 use std::time::Duration;
 
@@ -57,15 +64,129 @@ fn bench_vs_btreemap(c: &mut Criterion) {
             logs.clone(),
             &mut group,
         );
+        bench_logs_sequential_map::<OpenAddressingHashTable<_, _, FcfsRandomProbing>>(
+            "FcfsRandomProbing",
+            logs.clone(),
+            &mut group,
+        );
         bench_logs_sequential_map::<OpenAddressingHashTable<_, _, LcfsLinearProbing>>(
             "LcfsLinearProbing",
             logs.clone(),
             &mut group,
         );
+        bench_logs_sequential_map::<OpenAddressingHashTable<_, _, LcfsQuadraticProbing>>(
+            "LcfsQuadraticProbing",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<OpenAddressingHashTable<_, _, LcfsDoubleHashing>>(
+            "LcfsDoubleHashing",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<OpenAddressingHashTable<_, _, LcfsRandomProbing>>(
+            "LcfsRandomProbing",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<OpenAddressingHashTable<_, _, RobinHoodLinearProbing>>(
+            "RobinHoodLinearProbing",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<SwissTable<_, _>>(
+            "SwissTable",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<HopscotchHashTable<_, _>>(
+            "HopscotchHashTable",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<CuckooHashTable<_, _>>(
+            "CuckooHashTable",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<ChainingHashTable<_, _, LinkedListChain<_, _>>>(
+            "LinkedListChain",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<ChainingHashTable<_, _, SmallVecChain<_, _>>>(
+            "SmallVecChain",
+            logs.clone(),
+            &mut group,
+        );
+        bench_logs_sequential_map::<ChainingHashTable<_, _, MoveToFrontChain<_, _>>>(
+            "MoveToFrontChain",
+            logs.clone(),
+            &mut group,
+        );
     }
 }
 
-criterion_group!(bench, bench_vs_btreemap);
+// the inline value against the boxed value(the former layout of the bucket)
+fn bench_value_layout(c: &mut Criterion) {
+    let (insert, lookup, remove) = (30, 50, 20);
+    let logs = fuzz_logs(
+        300,
+        MAP_ALREADY_INSERTED,
+        MAP_TOTAL_OPS * insert / 100,
+        MAP_TOTAL_OPS * lookup / 100,
+        MAP_TOTAL_OPS * remove / 100,
+    );
+
+    let mut group = c.benchmark_group(format!(
+        "Value layout, Inserted {:+e}, Ops (I: {}%, L: {}%, R: {}%, total: {:+e})",
+        MAP_ALREADY_INSERTED, insert, lookup, remove, MAP_TOTAL_OPS
+    ));
+    group.measurement_time(Duration::from_secs(20));
+    group.sampling_mode(SamplingMode::Flat);
+    group.sample_size(20);
+    group.throughput(Throughput::Elements(MAP_TOTAL_OPS as u64));
+
+    bench_logs_sequential_map_with::<OpenAddressingHashTable<_, _, FcfsLinearProbing>, _, _>(
+        "FcfsLinearProbing, u64",
+        logs.clone(),
+        &mut group,
+        |key| key,
+    );
+    bench_logs_sequential_map_with::<OpenAddressingHashTable<_, _, FcfsLinearProbing>, _, _>(
+        "FcfsLinearProbing, Box<u64>",
+        logs.clone(),
+        &mut group,
+        Box::new,
+    );
+    bench_logs_sequential_map_with::<OpenAddressingHashTable<_, _, FcfsLinearProbing>, _, _>(
+        "FcfsLinearProbing, [u64; 16]",
+        logs.clone(),
+        &mut group,
+        |key| [key; 16],
+    );
+    bench_logs_sequential_map_with::<OpenAddressingHashTable<_, _, FcfsLinearProbing>, _, _>(
+        "FcfsLinearProbing, Box<[u64; 16]>",
+        logs.clone(),
+        &mut group,
+        |key| Box::new([key; 16]),
+    );
+    // LCFS moves the buckets on every insert
+    bench_logs_sequential_map_with::<OpenAddressingHashTable<_, _, LcfsLinearProbing>, _, _>(
+        "LcfsLinearProbing, [u64; 16]",
+        logs.clone(),
+        &mut group,
+        |key| [key; 16],
+    );
+    bench_logs_sequential_map_with::<OpenAddressingHashTable<_, _, LcfsLinearProbing>, _, _>(
+        "LcfsLinearProbing, Box<[u64; 16]>",
+        logs,
+        &mut group,
+        |key| Box::new([key; 16]),
+    );
+}
+
+criterion_group!(bench, bench_vs_btreemap, bench_value_layout);
 criterion_main! {
     bench,
 }
@@ -86,10 +86,23 @@ pub fn bench_logs_hashmap(mut logs: Vec<(Vec<u64>, Vec<Op>)>, c: &mut BenchmarkG
 
 pub fn bench_logs_sequential_map<M>(
     name: &str,
-    mut logs: Vec<(Vec<u64>, Vec<Op>)>,
+    logs: Vec<(Vec<u64>, Vec<Op>)>,
     c: &mut BenchmarkGroup<WallTime>,
 ) where
     M: HashMap<u64, u64>,
+{
+    bench_logs_sequential_map_with::<M, u64, _>(name, logs, c, |key| key);
+}
+
+// the value of the key is made by `value`, to compare the layouts of the value types
+pub fn bench_logs_sequential_map_with<M, V, F>(
+    name: &str,
+    mut logs: Vec<(Vec<u64>, Vec<Op>)>,
+    c: &mut BenchmarkGroup<WallTime>,
+    value: F,
+) where
+    M: HashMap<u64, V>,
+    F: Fn(u64) -> V,
 {
     c.bench_function(name, |b| {
         b.iter_custom(|iters| {
@@ -101,14 +114,14 @@ pub fn bench_logs_sequential_map<M>(
 
                 // pre-insert
                 for key in pre_inserted {
-                    let _ = map.insert(&key, key);
+                    let _ = map.insert(key, value(key));
                 }
 
                 let start = Instant::now();
                 for op in logs {
                     match op {
                         Op::Insert(key) => {
-                            let _ = black_box(map.insert(&key, key));
+                            let _ = black_box(map.insert(key, value(key)));
                         }
                         Op::Lookup(key) => {
                             let _ = black_box(map.lookup(&key));
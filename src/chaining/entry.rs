@@ -0,0 +1,135 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{OccupiedEntry, VacantEntry};
+
+use super::{Bucket, Chain, ChainingHashTable};
+
+pub struct Occupied<'a, K, V, C, S>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    pub(crate) table: &'a mut ChainingHashTable<K, V, C, S>,
+    pub(crate) key: K,
+    pub(crate) hash: u64,
+}
+
+impl<'a, K, V, C, S> Occupied<'a, K, V, C, S>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    // the bucket is looked up on each access, not to keep a pointer out of a reborrow
+    fn bucket(&self) -> &Bucket<K, V> {
+        match self.table.chain(self.hash).lookup(&self.key, self.hash) {
+            Some(bucket) => bucket,
+            None => unreachable!(),
+        }
+    }
+
+    fn bucket_mut(&mut self) -> &mut Bucket<K, V> {
+        match self
+            .table
+            .chain_mut(self.hash)
+            .lookup_mut(&self.key, self.hash)
+        {
+            Some(bucket) => bucket,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<'a, K, V, C, S> OccupiedEntry<'a> for Occupied<'a, K, V, C, S>
+where
+    K: PartialEq + Hash + 'a,
+    V: 'a,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.bucket().key
+    }
+
+    fn get(&self) -> &V {
+        &self.bucket().value
+    }
+
+    fn get_mut(&mut self) -> &mut V {
+        &mut self.bucket_mut().value
+    }
+
+    fn into_mut(self) -> &'a mut V {
+        match self
+            .table
+            .chain_mut(self.hash)
+            .lookup_mut(&self.key, self.hash)
+        {
+            Some(bucket) => &mut bucket.value,
+            None => unreachable!(),
+        }
+    }
+
+    fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    fn remove(self) -> V {
+        let bucket = self.table.chain_mut(self.hash).remove(&self.key, self.hash);
+        self.table.count -= 1;
+        self.table.shrink_if_sparse();
+
+        if let Ok(bucket) = bucket {
+            bucket.value
+        } else {
+            unreachable!()
+        }
+    }
+}
+
+pub struct Vacant<'a, K, V, C, S>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    pub(crate) table: &'a mut ChainingHashTable<K, V, C, S>,
+    pub(crate) key: K,
+    pub(crate) hash: u64,
+}
+
+impl<'a, K, V, C, S> VacantEntry<'a> for Vacant<'a, K, V, C, S>
+where
+    K: PartialEq + Hash + 'a,
+    V: 'a,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn into_key(self) -> K {
+        self.key
+    }
+
+    fn insert(self, value: V) -> &'a mut V {
+        let table = self.table;
+        table.count += 1;
+
+        let bucket = table.chain_mut(self.hash).push(Bucket {
+            key: self.key,
+            hash: self.hash,
+            value,
+        });
+
+        &mut bucket.value
+    }
+}
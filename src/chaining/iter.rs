@@ -0,0 +1,151 @@
+use std::hash::{BuildHasher, Hash};
+use std::slice;
+
+use crate::RawHashTable;
+
+use super::{Chain, ChainingHashTable};
+
+/// Iterator over the buckets of every chain.
+pub struct Iter<'a, K: PartialEq + 'a, V: 'a, C: Chain<K, V> + 'a> {
+    chains: slice::Iter<'a, C>,
+    chain: Option<C::Iter<'a>>,
+}
+
+impl<'a, K: PartialEq + 'a, V: 'a, C: Chain<K, V> + 'a> Iter<'a, K, V, C> {
+    pub(crate) fn new(table: &'a RawHashTable) -> Self {
+        let chains =
+            unsafe { slice::from_raw_parts(table.buckets.as_ptr() as *const C, table.mask + 1) };
+
+        Self {
+            chains: chains.iter(),
+            chain: None,
+        }
+    }
+}
+
+impl<'a, K: PartialEq + 'a, V: 'a, C: Chain<K, V> + 'a> Iterator for Iter<'a, K, V, C> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(bucket) = self.chain.as_mut().and_then(Iterator::next) {
+                return Some((&bucket.key, &bucket.value));
+            }
+
+            self.chain = Some(self.chains.next()?.iter());
+        }
+    }
+}
+
+pub struct IterMut<'a, K: PartialEq + 'a, V: 'a, C: Chain<K, V> + 'a> {
+    chains: slice::IterMut<'a, C>,
+    chain: Option<C::IterMut<'a>>,
+}
+
+impl<'a, K: PartialEq + 'a, V: 'a, C: Chain<K, V> + 'a> IterMut<'a, K, V, C> {
+    pub(crate) fn new(table: &'a mut RawHashTable) -> Self {
+        let chains =
+            unsafe { slice::from_raw_parts_mut(table.buckets.as_ptr() as *mut C, table.mask + 1) };
+
+        Self {
+            chains: chains.iter_mut(),
+            chain: None,
+        }
+    }
+}
+
+impl<'a, K: PartialEq + 'a, V: 'a, C: Chain<K, V> + 'a> Iterator for IterMut<'a, K, V, C> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(bucket) = self.chain.as_mut().and_then(Iterator::next) {
+                return Some((&bucket.key, &mut bucket.value));
+            }
+
+            self.chain = Some(self.chains.next()?.iter_mut());
+        }
+    }
+}
+
+/// Owning iterator, popping the buckets out of the chains.
+pub struct IntoIter<K, V, C, S>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    table: ChainingHashTable<K, V, C, S>,
+    index: usize,
+}
+
+impl<K, V, C, S> Iterator for IntoIter<K, V, C, S>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first_chain = self.table.inner.buckets.as_ptr() as *mut C;
+
+        while self.index <= self.table.inner.mask {
+            let chain = unsafe { &mut *first_chain.add(self.index) };
+
+            if let Some(bucket) = chain.pop() {
+                self.table.count -= 1;
+                return Some((bucket.key, bucket.value));
+            }
+
+            self.index += 1;
+        }
+
+        None
+    }
+}
+
+impl<K, V, C, S> IntoIterator for ChainingHashTable<K, V, C, S>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, C, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            table: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, K, V, C, S> IntoIterator for &'a ChainingHashTable<K, V, C, S>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(&self.inner)
+    }
+}
+
+impl<'a, K, V, C, S> IntoIterator for &'a mut ChainingHashTable<K, V, C, S>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IterMut::new(&mut self.inner)
+    }
+}
@@ -0,0 +1,159 @@
+use std::borrow::Borrow;
+
+use crate::InsertResult;
+
+use super::{Bucket, Chain};
+
+pub(crate) struct Node<K, V> {
+    pub(crate) bucket: Bucket<K, V>,
+    pub(crate) next: Option<Box<Node<K, V>>>,
+}
+
+pub struct Iter<'a, K, V> {
+    pub(crate) cursor: Option<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = &'a Bucket<K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.cursor.take()?;
+
+        self.cursor = node.next.as_deref();
+        Some(&node.bucket)
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    pub(crate) cursor: Option<&'a mut Node<K, V>>,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = &'a mut Bucket<K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.cursor.take()?;
+
+        self.cursor = node.next.as_deref_mut();
+        Some(&mut node.bucket)
+    }
+}
+
+/// The singly linked list, inserting a new bucket on the head.
+pub struct LinkedListChain<K, V> {
+    head: Option<Box<Node<K, V>>>,
+}
+
+impl<K, V> Default for LinkedListChain<K, V> {
+    fn default() -> Self {
+        Self { head: None }
+    }
+}
+
+impl<K, V> Drop for LinkedListChain<K, V> {
+    fn drop(&mut self) {
+        // drop iteratively not to overflow the stack on a long chain
+        let mut link = self.head.take();
+
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+impl<K: PartialEq, V> Chain<K, V> for LinkedListChain<K, V> {
+    type Iter<'a>
+        = Iter<'a, K, V>
+    where
+        Self: 'a;
+
+    type IterMut<'a>
+        = IterMut<'a, K, V>
+    where
+        Self: 'a;
+
+    fn insert(&mut self, bucket: Bucket<K, V>) -> InsertResult<Bucket<K, V>> {
+        if self.lookup(&bucket.key, bucket.hash).is_some() {
+            return InsertResult::AlreadyExist(bucket);
+        }
+
+        self.push(bucket);
+        InsertResult::Success
+    }
+
+    fn lookup_mut<Q>(&mut self, key: &Q, hash: u64) -> Option<&mut Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let mut cursor = self.head.as_deref_mut();
+
+        while let Some(node) = cursor {
+            if node.bucket.hash == hash && node.bucket.key.borrow() == key {
+                return Some(&mut node.bucket);
+            }
+
+            cursor = node.next.as_deref_mut();
+        }
+
+        None
+    }
+
+    fn remove<Q>(&mut self, key: &Q, hash: u64) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let link = find_link(&mut self.head, key, hash);
+        let mut node = link.take().ok_or(())?;
+
+        *link = node.next.take();
+        Ok(node.bucket)
+    }
+
+    fn push(&mut self, bucket: Bucket<K, V>) -> &mut Bucket<K, V> {
+        let next = self.head.take();
+        let node = self.head.insert(Box::new(Node { bucket, next }));
+
+        &mut node.bucket
+    }
+
+    fn pop(&mut self) -> Option<Bucket<K, V>> {
+        let mut node = self.head.take()?;
+
+        self.head = node.next.take();
+        Some(node.bucket)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            cursor: self.head.as_deref(),
+        }
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            cursor: self.head.as_deref_mut(),
+        }
+    }
+}
+
+// find the link pointing the node of the key, or the last link(None) if not found
+pub(crate) fn find_link<'a, K, V, Q>(
+    mut link: &'a mut Option<Box<Node<K, V>>>,
+    key: &Q,
+    hash: u64,
+) -> &'a mut Option<Box<Node<K, V>>>
+where
+    K: Borrow<Q>,
+    Q: ?Sized + PartialEq,
+{
+    while link
+        .as_ref()
+        .is_some_and(|node| !(node.bucket.hash == hash && node.bucket.key.borrow() == key))
+    {
+        link = &mut link.as_mut().unwrap().next;
+    }
+
+    link
+}
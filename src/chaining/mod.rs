@@ -0,0 +1,387 @@
+use std::alloc::{alloc, dealloc, Layout};
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, BuildHasherDefault};
+use std::marker::PhantomData;
+use std::{hash::Hash, ptr::NonNull};
+use std::{mem, ptr};
+
+use crate::{
+    size_for, Entry, HashMap, InsertResult, OccupiedEntry, RawHashTable, VacantEntry, INITIAL_SIZE,
+    LOAD_FACTOR,
+};
+
+mod entry;
+mod iter;
+mod linked_list;
+mod move_to_front;
+mod set;
+mod small_vec;
+
+pub use entry::{Occupied, Vacant};
+pub use iter::{IntoIter, Iter, IterMut};
+pub use linked_list::LinkedListChain;
+pub use move_to_front::MoveToFrontChain;
+pub use set::ChainingHashSet;
+pub use small_vec::SmallVecChain;
+
+pub struct Bucket<K, V> {
+    key: K,
+    hash: u64,
+    value: V,
+}
+
+/// The collision list hanging on each slot of `ChainingHashTable`.
+pub trait Chain<K: PartialEq, V>: Default {
+    type Iter<'a>: Iterator<Item = &'a Bucket<K, V>>
+    where
+        Self: 'a,
+        K: 'a,
+        V: 'a;
+
+    type IterMut<'a>: Iterator<Item = &'a mut Bucket<K, V>>
+    where
+        Self: 'a,
+        K: 'a,
+        V: 'a;
+
+    fn insert(&mut self, bucket: Bucket<K, V>) -> InsertResult<Bucket<K, V>>;
+    // the shared lookup never reorganizes the chain
+    fn lookup<Q>(&self, key: &Q, hash: u64) -> Option<&Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        self.iter()
+            .find(|bucket| bucket.hash == hash && bucket.key.borrow() == key)
+    }
+    // the lookup which may reorganize the chain(ex. MoveToFrontChain)
+    fn lookup_mut<Q>(&mut self, key: &Q, hash: u64) -> Option<&mut Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq;
+    #[allow(clippy::result_unit_err)]
+    fn remove<Q>(&mut self, key: &Q, hash: u64) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq;
+    // insert the bucket known not to be on the chain
+    fn push(&mut self, bucket: Bucket<K, V>) -> &mut Bucket<K, V>;
+    // take out any bucket, used to move the buckets on resize
+    fn pop(&mut self) -> Option<Bucket<K, V>>;
+    fn iter(&self) -> Self::Iter<'_>;
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+}
+
+fn alloc_chains<C: Default>(size: usize) -> NonNull<u8> {
+    let layout = match Layout::array::<C>(size) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Cannot initialize Chains"),
+    };
+
+    // allocate and init with empty chains
+    unsafe {
+        let raw = alloc(layout) as *mut C;
+
+        for i in 0..size {
+            ptr::write(raw.add(i), C::default());
+        }
+
+        NonNull::new(raw as *mut u8).unwrap()
+    }
+}
+
+fn dealloc_chains<C>(ptr: NonNull<u8>, size: usize) {
+    let layout = match Layout::array::<C>(size) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Cannot initialize Chains"),
+    };
+
+    unsafe {
+        dealloc(ptr.as_ptr(), layout);
+    }
+}
+
+pub struct ChainingHashTable<K, V, C, S = BuildHasherDefault<DefaultHasher>>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    hasher: S,
+    inner: RawHashTable,
+    count: usize,
+    load_factor: f32,
+    shrink_load_factor: f32, // 0.0 if the table does not shrink automatically
+    _marker: PhantomData<(K, V, C)>,
+}
+
+impl<K: PartialEq + Hash, V, C: Chain<K, V>, S: BuildHasher> Drop
+    for ChainingHashTable<K, V, C, S>
+{
+    fn drop(&mut self) {
+        let first_chain = self.inner.buckets.as_ptr() as *mut C;
+
+        for index in 0..=self.inner.mask {
+            unsafe { ptr::drop_in_place(first_chain.add(index)) };
+        }
+
+        dealloc_chains::<C>(self.inner.buckets, self.inner.mask + 1);
+    }
+}
+
+impl<K, V, C, S> ChainingHashTable<K, V, C, S>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher,
+{
+    pub fn new_with_properties(hasher: S, initial_size: usize, load_factor: f32) -> Self {
+        Self {
+            hasher,
+            inner: RawHashTable {
+                buckets: alloc_chains::<C>(initial_size),
+                mask: initial_size - 1,
+            },
+            count: 0,
+            load_factor,
+            shrink_load_factor: 0.0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Shrink the table automatically when the load drops under `shrink_load_factor` by
+    /// removals. It is disabled by default(`0.0`), and `SHRINK_LOAD_FACTOR` is a reasonable value.
+    pub fn set_shrink_load_factor(&mut self, shrink_load_factor: f32) {
+        self.shrink_load_factor = shrink_load_factor;
+    }
+
+    fn hash_one<Q: ?Sized + Hash>(&self, key: &Q) -> u64 {
+        self.hasher.hash_one(key)
+    }
+
+    // insert the bucket unless its key exists, and give the bucket back otherwise
+    fn insert_new(&mut self, bucket: Bucket<K, V>) -> Result<(), Bucket<K, V>> {
+        let hash = bucket.hash;
+
+        // the table grows only for the new key
+        if self.at_load_limit() && self.chain(hash).lookup(&bucket.key, hash).is_none() {
+            self.resize((self.inner.mask + 1) << 1);
+        }
+
+        match self.chain_mut(hash).insert(bucket) {
+            InsertResult::Success => {
+                self.count += 1;
+                Ok(())
+            }
+            InsertResult::AlreadyExist(bucket) => Err(bucket),
+            InsertResult::Full(_) => unreachable!(),
+        }
+    }
+
+    // remove the bucket of the key, which the map and the set take the value or the key from
+    fn remove_bucket<Q>(&mut self, key: &Q) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let hash = self.hash_one(key);
+        let bucket = self.chain_mut(hash).remove(key, hash)?;
+
+        self.count -= 1;
+        self.shrink_if_sparse();
+
+        Ok(bucket)
+    }
+
+    // shrink the table to the half load of the load factor if it gets sparse
+    fn shrink_if_sparse(&mut self) {
+        let size = self.inner.mask + 1;
+
+        if size > INITIAL_SIZE && (self.count as f32) < size as f32 * self.shrink_load_factor {
+            self.resize(size_for(self.count * 2, self.load_factor));
+        }
+    }
+
+    fn at_load_limit(&self) -> bool {
+        self.count >= ((self.inner.mask + 1) as f32 * self.load_factor) as usize
+    }
+
+    fn chain(&self, hash: u64) -> &C {
+        let first_chain = self.inner.buckets.as_ptr() as *const C;
+
+        unsafe { &*first_chain.add(hash as usize & self.inner.mask) }
+    }
+
+    fn chain_mut(&mut self, hash: u64) -> &mut C {
+        let first_chain = self.inner.buckets.as_ptr() as *mut C;
+
+        unsafe { &mut *first_chain.add(hash as usize & self.inner.mask) }
+    }
+
+    fn resize(&mut self, new_size: usize) {
+        let new_inner = RawHashTable {
+            buckets: alloc_chains::<C>(new_size),
+            mask: new_size - 1,
+        };
+
+        let old_inner = mem::replace(&mut self.inner, new_inner);
+        let old_chain = old_inner.buckets.as_ptr() as *mut C;
+
+        for index in 0..=old_inner.mask {
+            let mut chain = unsafe { ptr::read(old_chain.add(index)) };
+
+            while let Some(bucket) = chain.pop() {
+                let result = self.chain_mut(bucket.hash).insert(bucket);
+                assert!(matches!(result, InsertResult::Success));
+            }
+        }
+
+        dealloc_chains::<C>(old_inner.buckets, old_inner.mask + 1);
+    }
+}
+
+impl<K, V, C, S> HashMap<K, V, S> for ChainingHashTable<K, V, C, S>
+where
+    K: PartialEq + Hash,
+    C: Chain<K, V>,
+    S: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self::new_with_properties(S::default(), INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    fn with_hasher(hasher: S) -> Self {
+        Self::new_with_properties(hasher, INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::new_with_properties(S::default(), size_for(capacity, LOAD_FACTOR), LOAD_FACTOR)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Result<(), V> {
+        let hash = self.hash_one(&key);
+
+        self.insert_new(Bucket { key, hash, value })
+            .map_err(|bucket| bucket.value)
+    }
+
+    fn lookup<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let hash = self.hash_one(key);
+        let bucket = self.chain(hash).lookup(key, hash)?;
+
+        Some(&bucket.value)
+    }
+
+    fn lookup_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let hash = self.hash_one(key);
+        let bucket = self.chain_mut(hash).lookup_mut(key, hash)?;
+
+        Some(&mut bucket.value)
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        self.remove_bucket(key).map(|bucket| bucket.value)
+    }
+
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    fn capacity(&self) -> usize {
+        ((self.inner.mask + 1) as f32 * self.load_factor) as usize
+    }
+
+    fn tombstones(&self) -> usize {
+        // the removed bucket is unlinked from the chain
+        0
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        if self.count + additional > self.capacity() {
+            let new_size = size_for(self.count + additional, self.load_factor);
+            self.resize(new_size);
+        }
+    }
+
+    fn shrink_to(&mut self, min_capacity: usize) {
+        let new_size = size_for(self.count.max(min_capacity), self.load_factor);
+
+        if new_size < self.inner.mask + 1 {
+            self.resize(new_size);
+        }
+    }
+
+    fn insert_or_replace(&mut self, key: K, value: V) -> Option<V> {
+        self.entry(key).insert(value)
+    }
+
+    fn clear(&mut self) {
+        let first_chain = self.inner.buckets.as_ptr() as *mut C;
+
+        for index in 0..=self.inner.mask {
+            // dropping the old chain and leaving an empty one
+            unsafe { *first_chain.add(index) = C::default() };
+        }
+
+        self.count = 0;
+    }
+
+    fn entry<'a>(
+        &'a mut self,
+        key: K,
+    ) -> Entry<impl OccupiedEntry<'a, Key = K, Value = V>, impl VacantEntry<'a, Key = K, Value = V>>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let hash = self.hash_one(&key);
+
+        if self.chain_mut(hash).lookup_mut(&key, hash).is_some() {
+            return Entry::Occupied(Occupied {
+                table: self,
+                key,
+                hash,
+            });
+        }
+
+        // the table grows only for the new key
+        if self.at_load_limit() {
+            self.resize((self.inner.mask + 1) << 1);
+        }
+
+        Entry::Vacant(Vacant {
+            table: self,
+            key,
+            hash,
+        })
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        Iter::<K, V, C>::new(&self.inner)
+    }
+
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (&'a K, &'a mut V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        IterMut::<K, V, C>::new(&mut self.inner)
+    }
+}
@@ -0,0 +1,109 @@
+use std::borrow::Borrow;
+
+use crate::InsertResult;
+
+use super::{
+    linked_list::{find_link, Iter, IterMut, Node},
+    Bucket, Chain,
+};
+
+/// The singly linked list moving the found node to the head on each lookup by `&mut`, so the
+/// frequently accessed keys are found early. The shared lookup can not relink the nodes, so it
+/// leaves the list as it is.
+///
+/// The nodes are relinked rather than moved, so the references to the values are kept valid.
+pub struct MoveToFrontChain<K, V> {
+    head: Option<Box<Node<K, V>>>,
+}
+
+impl<K, V> Default for MoveToFrontChain<K, V> {
+    fn default() -> Self {
+        Self { head: None }
+    }
+}
+
+impl<K, V> Drop for MoveToFrontChain<K, V> {
+    fn drop(&mut self) {
+        // drop iteratively not to overflow the stack on a long chain
+        let mut link = self.head.take();
+
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+impl<K: PartialEq, V> Chain<K, V> for MoveToFrontChain<K, V> {
+    type Iter<'a>
+        = Iter<'a, K, V>
+    where
+        Self: 'a;
+
+    type IterMut<'a>
+        = IterMut<'a, K, V>
+    where
+        Self: 'a;
+
+    fn insert(&mut self, bucket: Bucket<K, V>) -> InsertResult<Bucket<K, V>> {
+        if find_link(&mut self.head, &bucket.key, bucket.hash).is_some() {
+            return InsertResult::AlreadyExist(bucket);
+        }
+
+        self.push(bucket);
+        InsertResult::Success
+    }
+
+    fn lookup_mut<Q>(&mut self, key: &Q, hash: u64) -> Option<&mut Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let link = find_link(&mut self.head, key, hash);
+        let mut node = link.take()?;
+
+        *link = node.next.take();
+        node.next = self.head.take();
+        self.head = Some(node);
+
+        self.head.as_deref_mut().map(|node| &mut node.bucket)
+    }
+
+    fn remove<Q>(&mut self, key: &Q, hash: u64) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let link = find_link(&mut self.head, key, hash);
+        let mut node = link.take().ok_or(())?;
+
+        *link = node.next.take();
+        Ok(node.bucket)
+    }
+
+    fn push(&mut self, bucket: Bucket<K, V>) -> &mut Bucket<K, V> {
+        let next = self.head.take();
+        let node = self.head.insert(Box::new(Node { bucket, next }));
+
+        &mut node.bucket
+    }
+
+    fn pop(&mut self) -> Option<Bucket<K, V>> {
+        let mut node = self.head.take()?;
+
+        self.head = node.next.take();
+        Some(node.bucket)
+    }
+
+    // iteration does not reorganize the list
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            cursor: self.head.as_deref(),
+        }
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        IterMut {
+            cursor: self.head.as_deref_mut(),
+        }
+    }
+}
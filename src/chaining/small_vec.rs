@@ -0,0 +1,106 @@
+use std::{borrow::Borrow, iter, slice};
+
+use crate::InsertResult;
+
+use super::{Bucket, Chain};
+
+/// The vector keeping the first `N` buckets inline on the table, and spilling the rest to the
+/// heap.
+pub struct SmallVecChain<K, V, const N: usize = 4> {
+    inline: [Option<Bucket<K, V>>; N],
+    spilled: Vec<Bucket<K, V>>,
+}
+
+impl<K, V, const N: usize> Default for SmallVecChain<K, V, N> {
+    fn default() -> Self {
+        Self {
+            inline: [(); N].map(|_| None),
+            spilled: Vec::new(),
+        }
+    }
+}
+
+impl<K: PartialEq, V, const N: usize> Chain<K, V> for SmallVecChain<K, V, N> {
+    type Iter<'a>
+        = iter::Chain<
+        iter::Flatten<slice::Iter<'a, Option<Bucket<K, V>>>>,
+        slice::Iter<'a, Bucket<K, V>>,
+    >
+    where
+        Self: 'a;
+
+    type IterMut<'a>
+        = iter::Chain<
+        iter::Flatten<slice::IterMut<'a, Option<Bucket<K, V>>>>,
+        slice::IterMut<'a, Bucket<K, V>>,
+    >
+    where
+        Self: 'a;
+
+    fn insert(&mut self, bucket: Bucket<K, V>) -> InsertResult<Bucket<K, V>> {
+        if self.lookup(&bucket.key, bucket.hash).is_some() {
+            return InsertResult::AlreadyExist(bucket);
+        }
+
+        self.push(bucket);
+        InsertResult::Success
+    }
+
+    fn lookup_mut<Q>(&mut self, key: &Q, hash: u64) -> Option<&mut Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        self.iter_mut()
+            .find(|bucket| bucket.hash == hash && bucket.key.borrow() == key)
+    }
+
+    fn remove<Q>(&mut self, key: &Q, hash: u64) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let found = |bucket: &Bucket<K, V>| bucket.hash == hash && bucket.key.borrow() == key;
+
+        if let Some(slot) = self
+            .inline
+            .iter_mut()
+            .find(|slot| slot.as_ref().is_some_and(found))
+        {
+            // fill the inline slot with a spilled one
+            let bucket = slot.take();
+            *slot = self.spilled.pop();
+
+            return bucket.ok_or(());
+        }
+
+        let index = self.spilled.iter().position(found).ok_or(())?;
+        Ok(self.spilled.swap_remove(index))
+    }
+
+    fn push(&mut self, bucket: Bucket<K, V>) -> &mut Bucket<K, V> {
+        if let Some(slot) = self.inline.iter_mut().find(|slot| slot.is_none()) {
+            slot.insert(bucket)
+        } else {
+            self.spilled.push(bucket);
+            self.spilled.last_mut().unwrap()
+        }
+    }
+
+    fn pop(&mut self) -> Option<Bucket<K, V>> {
+        self.spilled
+            .pop()
+            .or_else(|| self.inline.iter_mut().find_map(Option::take))
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.inline.iter().flatten().chain(self.spilled.iter())
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        self.inline
+            .iter_mut()
+            .flatten()
+            .chain(self.spilled.iter_mut())
+    }
+}
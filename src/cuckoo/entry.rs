@@ -0,0 +1,97 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{OccupiedEntry, VacantEntry};
+
+use super::{Bucket, CuckooHashTable, Location};
+
+pub struct Occupied<'a, K, V, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    pub(crate) table: &'a mut CuckooHashTable<K, V, S, H>,
+    pub(crate) location: Location,
+}
+
+impl<'a, K, V, S, H> OccupiedEntry<'a> for Occupied<'a, K, V, S, H>
+where
+    K: PartialEq + Hash + 'a,
+    V: 'a,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.table.bucket(self.location).key
+    }
+
+    fn get(&self) -> &V {
+        &self.table.bucket(self.location).value
+    }
+
+    fn get_mut(&mut self) -> &mut V {
+        &mut self.table.bucket_mut(self.location).value
+    }
+
+    fn into_mut(self) -> &'a mut V {
+        &mut self.table.bucket_mut(self.location).value
+    }
+
+    fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    fn remove(self) -> V {
+        let bucket = self.table.take(self.location);
+        self.table.shrink_if_sparse();
+
+        bucket.value
+    }
+}
+
+pub struct Vacant<'a, K, V, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    pub(crate) table: &'a mut CuckooHashTable<K, V, S, H>,
+    pub(crate) key: K,
+    pub(crate) hash: u64,
+}
+
+impl<'a, K, V, S, H> VacantEntry<'a> for Vacant<'a, K, V, S, H>
+where
+    K: PartialEq + Hash + 'a,
+    V: 'a,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn into_key(self) -> K {
+        self.key
+    }
+
+    fn insert(self, value: V) -> &'a mut V {
+        let table = self.table;
+        table.count += 1;
+
+        let location = table.insert_bucket(Bucket {
+            second_hash: table.second_hash_one(&self.key),
+            key: self.key,
+            hash: self.hash,
+            value,
+        });
+
+        &mut table.bucket_mut(location).value
+    }
+}
@@ -0,0 +1,155 @@
+use std::hash::{BuildHasher, Hash};
+use std::slice;
+
+use crate::RawHashTable;
+
+use super::{Bucket, CuckooHashTable};
+
+/// Iterator over the slots holding a bucket, and then the stash.
+pub struct Iter<'a, K, V> {
+    slots: slice::Iter<'a, Option<Bucket<K, V>>>,
+    stash: slice::Iter<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    pub(crate) fn new(table: &'a RawHashTable, slots: usize, stash: &'a [Bucket<K, V>]) -> Self {
+        let slots = unsafe {
+            slice::from_raw_parts(table.buckets.as_ptr() as *const Option<Bucket<K, V>>, slots)
+        };
+
+        Self {
+            slots: slots.iter(),
+            stash: stash.iter(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bucket = self
+            .slots
+            .find_map(Option::as_ref)
+            .or_else(|| self.stash.next())?;
+
+        Some((&bucket.key, &bucket.value))
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    slots: slice::IterMut<'a, Option<Bucket<K, V>>>,
+    stash: slice::IterMut<'a, Bucket<K, V>>,
+}
+
+impl<'a, K, V> IterMut<'a, K, V> {
+    pub(crate) fn new(
+        table: &'a mut RawHashTable,
+        slots: usize,
+        stash: &'a mut [Bucket<K, V>],
+    ) -> Self {
+        let slots = unsafe {
+            slice::from_raw_parts_mut(table.buckets.as_ptr() as *mut Option<Bucket<K, V>>, slots)
+        };
+
+        Self {
+            slots: slots.iter_mut(),
+            stash: stash.iter_mut(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bucket = self
+            .slots
+            .find_map(Option::as_mut)
+            .or_else(|| self.stash.next())?;
+
+        Some((&bucket.key, &mut bucket.value))
+    }
+}
+
+/// Owning iterator, taking out the buckets from the table. The table still owns the slot array
+/// and frees it on drop.
+pub struct IntoIter<K, V, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    table: CuckooHashTable<K, V, S, H>,
+    index: usize,
+}
+
+impl<K, V, S, H> Iterator for IntoIter<K, V, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.table.slots() {
+            let slot = self.table.slot_mut(self.index);
+            self.index += 1;
+
+            if let Some(bucket) = slot.take() {
+                return Some((bucket.key, bucket.value));
+            }
+        }
+
+        let bucket = self.table.stash.pop()?;
+        Some((bucket.key, bucket.value))
+    }
+}
+
+impl<K, V, S, H> IntoIterator for CuckooHashTable<K, V, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, S, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            table: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, K, V, S, H> IntoIterator for &'a CuckooHashTable<K, V, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(&self.inner, self.slots(), &self.stash)
+    }
+}
+
+impl<'a, K, V, S, H> IntoIterator for &'a mut CuckooHashTable<K, V, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let slots = self.slots();
+
+        IterMut::new(&mut self.inner, slots, &mut self.stash)
+    }
+}
@@ -0,0 +1,604 @@
+use std::alloc::{alloc, dealloc, Layout};
+use std::borrow::Borrow;
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::hash::{BuildHasher, BuildHasherDefault};
+use std::marker::PhantomData;
+use std::{hash::Hash, ptr::NonNull};
+use std::{mem, ptr};
+
+use crate::{
+    size_for, Entry, HashMap, OccupiedEntry, RawHashTable, VacantEntry, INITIAL_SIZE, LOAD_FACTOR,
+};
+
+mod entry;
+mod iter;
+mod set;
+
+pub use entry::{Occupied, Vacant};
+pub use iter::{IntoIter, Iter, IterMut};
+pub use set::CuckooHashSet;
+
+// the default number of the tables, each of which has its own hash function
+pub const TABLES: usize = 2;
+// the default number of the slots on each bucket of the tables
+pub const WAYS: usize = 4;
+
+// the bound of the displacements for an insert, before the bucket goes to the stash
+const MAX_DISPLACEMENT: usize = 128;
+// the number of the buckets which can be failed to be placed on the tables
+const STASH_SIZE: usize = 4;
+// the number of the rehashes by the new seeds before growing the tables
+const MAX_REHASHES: usize = 4;
+
+pub struct Bucket<K, V> {
+    key: K,
+    hash: u64,
+    second_hash: u64, // hashed by the second hasher with the seed of the table
+    value: V,
+}
+
+// where the bucket lives
+#[derive(Clone, Copy)]
+pub(crate) enum Location {
+    Slot(usize),
+    Stash(usize),
+}
+
+fn alloc_slots<K, V>(len: usize) -> NonNull<u8> {
+    let layout = match Layout::array::<Option<Bucket<K, V>>>(len) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Cannot initialize Slots"),
+    };
+
+    // allocate and init with empty slots
+    unsafe {
+        let raw = alloc(layout) as *mut Option<Bucket<K, V>>;
+
+        for i in 0..len {
+            ptr::write(raw.add(i), None);
+        }
+
+        NonNull::new(raw as *mut u8).unwrap()
+    }
+}
+
+fn dealloc_slots<K, V>(ptr: NonNull<u8>, len: usize) {
+    let layout = match Layout::array::<Option<Bucket<K, V>>>(len) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Cannot initialize Slots"),
+    };
+
+    unsafe {
+        dealloc(ptr.as_ptr(), layout);
+    }
+}
+
+/// Cuckoo hashing places each key on one of its `tables` candidate buckets, one for each table,
+/// and each bucket has `ways` slots. So the lookup visits at most `tables * ways` slots and the
+/// small stash. The insert displaces the buckets along the tables when every candidate is full,
+/// and the table is rehashed by the new seed of the second hasher on the cycle.
+///
+/// The candidate on the table `i` is `hash + i * second_hash`, where `hash` is by `S` and
+/// `second_hash` is by the seeded `H`.
+pub struct CuckooHashTable<K, V, S = BuildHasherDefault<DefaultHasher>, H = RandomState>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    hasher: S,
+    second_hasher: H,
+    seed: u64,
+    inner: RawHashTable, // `tables` tables of `mask + 1` buckets of `ways` slots
+    tables: usize,
+    ways: usize,
+    stash: Vec<Bucket<K, V>>,
+    count: usize, // the number of live buckets, including the stash
+    load_factor: f32,
+    shrink_load_factor: f32, // 0.0 if the table does not shrink automatically
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V, S, H> Drop for CuckooHashTable<K, V, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    fn drop(&mut self) {
+        let first_slot = self.inner.buckets.as_ptr() as *mut Option<Bucket<K, V>>;
+
+        for index in 0..self.slots() {
+            unsafe { ptr::drop_in_place(first_slot.add(index)) };
+        }
+
+        dealloc_slots::<K, V>(self.inner.buckets, self.slots());
+    }
+}
+
+impl<K, V, S, H> CuckooHashTable<K, V, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    /// `initial_size` is the number of the buckets on each table, and `ways` is 4 for the
+    /// bucketized cuckoo hashing or 1 for the classic one.
+    pub fn new_with_properties(
+        hasher: S,
+        second_hasher: H,
+        tables: usize,
+        ways: usize,
+        initial_size: usize,
+        load_factor: f32,
+    ) -> Self {
+        assert!(tables >= 2, "Cuckoo hashing needs two tables at least.");
+        assert!(ways >= 1, "The bucket needs a slot at least.");
+
+        Self {
+            hasher,
+            second_hasher,
+            seed: 0,
+            inner: RawHashTable {
+                buckets: alloc_slots::<K, V>(tables * ways * initial_size),
+                mask: initial_size - 1,
+            },
+            tables,
+            ways,
+            stash: Vec::with_capacity(STASH_SIZE),
+            count: 0,
+            load_factor,
+            shrink_load_factor: 0.0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Shrink the table automatically when the load drops under `shrink_load_factor` by
+    /// removals. It is disabled by default(`0.0`), and `SHRINK_LOAD_FACTOR` is a reasonable value.
+    pub fn set_shrink_load_factor(&mut self, shrink_load_factor: f32) {
+        self.shrink_load_factor = shrink_load_factor;
+    }
+
+    fn hash_one<Q: ?Sized + Hash>(&self, key: &Q) -> u64 {
+        self.hasher.hash_one(key)
+    }
+
+    fn second_hash_one<Q: ?Sized + Hash>(&self, key: &Q) -> u64 {
+        self.second_hasher.hash_one((self.seed, key))
+    }
+
+    // the number of all slots on the tables
+    fn slots(&self) -> usize {
+        self.tables * self.ways * (self.inner.mask + 1)
+    }
+
+    // the smallest size of each table, which holds `capacity` entries
+    fn size_for(&self, capacity: usize) -> usize {
+        size_for(capacity.div_ceil(self.tables * self.ways), self.load_factor)
+    }
+
+    fn slot(&self, index: usize) -> &Option<Bucket<K, V>> {
+        let first_slot = self.inner.buckets.as_ptr() as *const Option<Bucket<K, V>>;
+
+        unsafe { &*first_slot.add(index) }
+    }
+
+    fn slot_mut(&mut self, index: usize) -> &mut Option<Bucket<K, V>> {
+        let first_slot = self.inner.buckets.as_ptr() as *mut Option<Bucket<K, V>>;
+
+        unsafe { &mut *first_slot.add(index) }
+    }
+
+    // the index of the `way`-th slot of the candidate bucket on the table `table`
+    fn slot_index(&self, table: usize, hash: u64, second_hash: u64, way: usize) -> usize {
+        let bucket =
+            hash.wrapping_add((table as u64).wrapping_mul(second_hash)) as usize & self.inner.mask;
+
+        ((table * (self.inner.mask + 1)) + bucket) * self.ways + way
+    }
+
+    fn find<Q>(&self, key: &Q, hash: u64) -> Option<Location>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let second_hash = self.second_hash_one(key);
+
+        for table in 0..self.tables {
+            for way in 0..self.ways {
+                let index = self.slot_index(table, hash, second_hash, way);
+
+                if let Some(bucket) = self.slot(index) {
+                    if bucket.hash == hash && bucket.key.borrow() == key {
+                        return Some(Location::Slot(index));
+                    }
+                }
+            }
+        }
+
+        self.stash
+            .iter()
+            .position(|bucket| bucket.hash == hash && bucket.key.borrow() == key)
+            .map(Location::Stash)
+    }
+
+    fn bucket(&self, location: Location) -> &Bucket<K, V> {
+        match location {
+            Location::Slot(index) => self.slot(index).as_ref().unwrap(),
+            Location::Stash(index) => &self.stash[index],
+        }
+    }
+
+    fn bucket_mut(&mut self, location: Location) -> &mut Bucket<K, V> {
+        match location {
+            Location::Slot(index) => self.slot_mut(index).as_mut().unwrap(),
+            Location::Stash(index) => &mut self.stash[index],
+        }
+    }
+
+    // the empty slot among the candidates of the bucket
+    fn vacancy(&self, bucket: &Bucket<K, V>) -> Option<usize> {
+        for table in 0..self.tables {
+            for way in 0..self.ways {
+                let index = self.slot_index(table, bucket.hash, bucket.second_hash, way);
+
+                if self.slot(index).is_none() {
+                    return Some(index);
+                }
+            }
+        }
+
+        None
+    }
+
+    // place the bucket known not to be on the table, by displacing the buckets along the tables
+    // up to `MAX_DISPLACEMENT` times and then stashing, and return where the given bucket is. On
+    // failure, the displacements are undone and the given bucket is returned back.
+    fn place(&mut self, mut bucket: Bucket<K, V>) -> Result<Location, Bucket<K, V>> {
+        let mut table = 0;
+        let mut displaced = Vec::new();
+        let mut placed = None; // None while the given bucket is carried
+
+        for displacement in 0..MAX_DISPLACEMENT {
+            if let Some(index) = self.vacancy(&bucket) {
+                *self.slot_mut(index) = Some(bucket);
+                return Ok(placed.unwrap_or(Location::Slot(index)));
+            }
+
+            // every candidate is full, so kick out the victim and carry it to its next table
+            let way = displacement % self.ways;
+            let index = self.slot_index(table, bucket.hash, bucket.second_hash, way);
+
+            bucket = self.slot_mut(index).replace(bucket).unwrap();
+            displaced.push(index);
+            table = (table + 1) % self.tables;
+
+            placed = match placed {
+                None => Some(Location::Slot(index)),
+                Some(Location::Slot(given)) if given == index => None,
+                placed => placed,
+            };
+        }
+
+        if self.stash.len() < STASH_SIZE {
+            self.stash.push(bucket);
+            Ok(placed.unwrap_or(Location::Stash(self.stash.len() - 1)))
+        } else {
+            // carry the buckets back along the displacements, to get the given bucket again
+            for index in displaced.into_iter().rev() {
+                bucket = self.slot_mut(index).replace(bucket).unwrap();
+            }
+
+            Err(bucket)
+        }
+    }
+
+    // place the bucket, rehashing the table by the new seeds on the cycle, and return where the
+    // bucket is
+    fn insert_bucket(&mut self, mut bucket: Bucket<K, V>) -> Location {
+        let mut rehashes = 0;
+
+        loop {
+            match self.place(bucket) {
+                Ok(location) => return location,
+                Err(failed) => bucket = failed,
+            }
+
+            // the other buckets are placed again first, and grow the tables after the retries
+            rehashes += 1;
+            let size = self.inner.mask + 1;
+            let new_size = if rehashes % MAX_REHASHES == 0 {
+                size << 1
+            } else {
+                size
+            };
+
+            self.rehash(new_size, Vec::new(), true);
+            bucket.second_hash = self.second_hash_one(&bucket.key);
+        }
+    }
+
+    fn take(&mut self, location: Location) -> Bucket<K, V> {
+        let bucket = match location {
+            Location::Slot(index) => self.slot_mut(index).take().unwrap(),
+            Location::Stash(index) => self.stash.swap_remove(index),
+        };
+        self.count -= 1;
+
+        // the removal may make room for the stashed buckets
+        let mut index = 0;
+        while index < self.stash.len() {
+            if let Some(vacancy) = self.vacancy(&self.stash[index]) {
+                let bucket = self.stash.swap_remove(index);
+                *self.slot_mut(vacancy) = Some(bucket);
+            } else {
+                index += 1;
+            }
+        }
+
+        bucket
+    }
+
+    fn reserve_one(&mut self) {
+        if self.count >= self.capacity_of(self.inner.mask + 1) {
+            self.rehash((self.inner.mask + 1) << 1, Vec::new(), false);
+        }
+    }
+
+    fn capacity_of(&self, size: usize) -> usize {
+        ((self.tables * self.ways * size) as f32 * self.load_factor) as usize
+    }
+
+    // insert the bucket unless its key exists, and give the bucket back otherwise
+    fn insert_new(&mut self, bucket: Bucket<K, V>) -> Result<(), Bucket<K, V>> {
+        if self.find(&bucket.key, bucket.hash).is_some() {
+            return Err(bucket);
+        }
+
+        self.reserve_one();
+        self.insert_bucket(bucket);
+        self.count += 1;
+
+        Ok(())
+    }
+
+    // remove the bucket of the key, which the map and the set take the value or the key from
+    fn remove_bucket<Q>(&mut self, key: &Q) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let location = self.find(key, self.hash_one(key)).ok_or(())?;
+        let bucket = self.take(location);
+
+        self.shrink_if_sparse();
+
+        Ok(bucket)
+    }
+
+    // shrink the table to the half load of the load factor if it gets sparse
+    fn shrink_if_sparse(&mut self) {
+        let size = self.inner.mask + 1;
+
+        if size > INITIAL_SIZE
+            && (self.count as f32) < self.slots() as f32 * self.shrink_load_factor
+        {
+            self.rehash(self.size_for(self.count * 2), Vec::new(), false);
+        }
+    }
+
+    // move every bucket to the new tables of `new_size` with `pending` buckets. If `reseed`, the
+    // second hashes are changed by the new seed. On the cycle, it retries with the new seed and
+    // grows the tables after `MAX_REHASHES` retries.
+    fn rehash(&mut self, mut new_size: usize, mut pending: Vec<Bucket<K, V>>, mut reseed: bool) {
+        let mut rehashes = 0;
+
+        loop {
+            let old_slots = self.slots();
+            let new_inner = RawHashTable {
+                buckets: alloc_slots::<K, V>(self.tables * self.ways * new_size),
+                mask: new_size - 1,
+            };
+
+            let old_inner = mem::replace(&mut self.inner, new_inner);
+            let old_slot = old_inner.buckets.as_ptr() as *mut Option<Bucket<K, V>>;
+
+            for index in 0..old_slots {
+                pending.extend(unsafe { ptr::read(old_slot.add(index)) });
+            }
+
+            dealloc_slots::<K, V>(old_inner.buckets, old_slots);
+            pending.append(&mut self.stash);
+
+            if reseed {
+                self.seed = self.seed.wrapping_add(1);
+
+                for bucket in pending.iter_mut() {
+                    bucket.second_hash = self.second_hasher.hash_one((self.seed, &bucket.key));
+                }
+            }
+
+            while let Some(bucket) = pending.pop() {
+                if let Err(bucket) = self.place(bucket) {
+                    pending.push(bucket);
+                    break;
+                }
+            }
+
+            if pending.is_empty() {
+                return;
+            }
+
+            reseed = true;
+            rehashes += 1;
+
+            if rehashes % MAX_REHASHES == 0 {
+                new_size <<= 1;
+            }
+        }
+    }
+}
+
+impl<K, V, S, H> HashMap<K, V, S> for CuckooHashTable<K, V, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher + Default,
+    H: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self::new_with_properties(
+            S::default(),
+            H::default(),
+            TABLES,
+            WAYS,
+            INITIAL_SIZE,
+            LOAD_FACTOR,
+        )
+    }
+
+    fn with_hasher(hasher: S) -> Self {
+        Self::new_with_properties(
+            hasher,
+            H::default(),
+            TABLES,
+            WAYS,
+            INITIAL_SIZE,
+            LOAD_FACTOR,
+        )
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::new_with_properties(
+            S::default(),
+            H::default(),
+            TABLES,
+            WAYS,
+            size_for(capacity.div_ceil(TABLES * WAYS), LOAD_FACTOR),
+            LOAD_FACTOR,
+        )
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Result<(), V> {
+        let bucket = Bucket {
+            second_hash: self.second_hash_one(&key),
+            hash: self.hash_one(&key),
+            key,
+            value,
+        };
+
+        self.insert_new(bucket).map_err(|bucket| bucket.value)
+    }
+
+    fn lookup<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let location = self.find(key, self.hash_one(key))?;
+
+        Some(&self.bucket(location).value)
+    }
+
+    fn lookup_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let location = self.find(key, self.hash_one(key))?;
+
+        Some(&mut self.bucket_mut(location).value)
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        self.remove_bucket(key).map(|bucket| bucket.value)
+    }
+
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity_of(self.inner.mask + 1)
+    }
+
+    fn tombstones(&self) -> usize {
+        // the removed bucket leaves its slot empty
+        0
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        if self.count + additional > self.capacity() {
+            let new_size = self.size_for(self.count + additional);
+            self.rehash(new_size, Vec::new(), false);
+        }
+    }
+
+    fn shrink_to(&mut self, min_capacity: usize) {
+        let new_size = self.size_for(self.count.max(min_capacity));
+
+        if new_size < self.inner.mask + 1 {
+            self.rehash(new_size, Vec::new(), false);
+        }
+    }
+
+    fn insert_or_replace(&mut self, key: K, value: V) -> Option<V> {
+        self.entry(key).insert(value)
+    }
+
+    fn clear(&mut self) {
+        for index in 0..self.slots() {
+            *self.slot_mut(index) = None;
+        }
+
+        self.stash.clear();
+        self.count = 0;
+    }
+
+    fn entry<'a>(
+        &'a mut self,
+        key: K,
+    ) -> Entry<impl OccupiedEntry<'a, Key = K, Value = V>, impl VacantEntry<'a, Key = K, Value = V>>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let hash = self.hash_one(&key);
+
+        if let Some(location) = self.find(&key, hash) {
+            Entry::Occupied(Occupied {
+                table: self,
+                location,
+            })
+        } else {
+            self.reserve_one();
+
+            Entry::Vacant(Vacant {
+                table: self,
+                key,
+                hash,
+            })
+        }
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        Iter::new(&self.inner, self.slots(), &self.stash)
+    }
+
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (&'a K, &'a mut V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let slots = self.slots();
+
+        IterMut::new(&mut self.inner, slots, &mut self.stash)
+    }
+}
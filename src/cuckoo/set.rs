@@ -0,0 +1,158 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
+use std::iter::{FromIterator, Map};
+
+use crate::{HashMap, HashSet};
+
+use super::{Bucket, CuckooHashTable, IntoIter, Iter};
+
+/// The set on `CuckooHashTable`, whose buckets hold the keys only since the value is `()`.
+pub struct CuckooHashSet<K, S = BuildHasherDefault<DefaultHasher>, H = RandomState>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    table: CuckooHashTable<K, (), S, H>,
+}
+
+impl<K, S, H> CuckooHashSet<K, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    pub fn new_with_properties(
+        hasher: S,
+        second_hasher: H,
+        tables: usize,
+        ways: usize,
+        initial_size: usize,
+        load_factor: f32,
+    ) -> Self {
+        Self {
+            table: CuckooHashTable::new_with_properties(
+                hasher,
+                second_hasher,
+                tables,
+                ways,
+                initial_size,
+                load_factor,
+            ),
+        }
+    }
+}
+
+impl<K, S, H> HashSet<K, S> for CuckooHashSet<K, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher + Default,
+    H: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self {
+            table: CuckooHashTable::new(),
+        }
+    }
+
+    fn with_hasher(hasher: S) -> Self {
+        Self {
+            table: CuckooHashTable::with_hasher(hasher),
+        }
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            table: CuckooHashTable::with_capacity(capacity),
+        }
+    }
+
+    fn insert(&mut self, key: K) -> Result<(), K> {
+        let bucket = Bucket {
+            second_hash: self.table.second_hash_one(&key),
+            hash: self.table.hash_one(&key),
+            key,
+            value: (),
+        };
+
+        self.table.insert_new(bucket).map_err(|bucket| bucket.key)
+    }
+
+    fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        self.table.lookup(key).is_some()
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<K, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        self.table.remove_bucket(key).map(|bucket| bucket.key)
+    }
+
+    fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    fn clear(&mut self) {
+        self.table.clear();
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a K>
+    where
+        K: 'a,
+    {
+        self.into_iter()
+    }
+}
+
+impl<K, S, H> FromIterator<K> for CuckooHashSet<K, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher + Default,
+    H: BuildHasher + Default,
+{
+    // the duplicated keys are dropped
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = Self::new();
+
+        for key in iter {
+            let _ = set.insert(key);
+        }
+
+        set
+    }
+}
+
+impl<K, S, H> IntoIterator for CuckooHashSet<K, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    type Item = K;
+    type IntoIter = Map<IntoIter<K, (), S, H>, fn((K, ())) -> K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.table.into_iter().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K, S, H> IntoIterator for &'a CuckooHashSet<K, S, H>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+    H: BuildHasher,
+{
+    type Item = &'a K;
+    type IntoIter = Map<Iter<'a, K, ()>, fn((&'a K, &'a ())) -> &'a K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (&self.table).into_iter().map(|(key, _)| key)
+    }
+}
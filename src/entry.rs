@@ -0,0 +1,83 @@
+/// A view into a single key of a map, which is either occupied or vacant.
+///
+/// Each map provides its own occupied and vacant entries, found by a single walk of the table.
+pub enum Entry<Occupied, Vacant> {
+    Occupied(Occupied),
+    Vacant(Vacant),
+}
+
+pub trait OccupiedEntry<'a> {
+    type Key: 'a;
+    type Value: 'a;
+
+    fn key(&self) -> &Self::Key;
+    fn get(&self) -> &Self::Value;
+    fn get_mut(&mut self) -> &mut Self::Value;
+    fn into_mut(self) -> &'a mut Self::Value;
+    // replace the value, and return the old one
+    fn insert(&mut self, value: Self::Value) -> Self::Value;
+    fn remove(self) -> Self::Value;
+}
+
+pub trait VacantEntry<'a> {
+    type Key: 'a;
+    type Value: 'a;
+
+    fn key(&self) -> &Self::Key;
+    fn into_key(self) -> Self::Key;
+    fn insert(self, value: Self::Value) -> &'a mut Self::Value;
+}
+
+impl<'a, O, V> Entry<O, V>
+where
+    O: OccupiedEntry<'a>,
+    V: VacantEntry<'a, Key = O::Key, Value = O::Value>,
+{
+    pub fn key(&self) -> &O::Key {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: O::Value) -> &'a mut O::Value {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> O::Value>(self, default: F) -> &'a mut O::Value {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn and_modify<F: FnOnce(&mut O::Value)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+
+        self
+    }
+
+    /// Set the value of the entry, and return the old value if it was occupied.
+    pub fn insert(self, value: O::Value) -> Option<O::Value> {
+        match self {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Remove the entry, and return the value if it was occupied.
+    pub fn remove(self) -> Option<O::Value> {
+        match self {
+            Entry::Occupied(entry) => Some(entry.remove()),
+            Entry::Vacant(_) => None,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{OccupiedEntry, VacantEntry};
+
+use super::{Bucket, HopscotchHashTable};
+
+pub struct Occupied<'a, K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    pub(crate) table: &'a mut HopscotchHashTable<K, V, S>,
+    pub(crate) index: usize,
+}
+
+impl<'a, K, V, S> Occupied<'a, K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    fn bucket(&self) -> &Bucket<K, V> {
+        self.table.slot(self.index).bucket.as_ref().unwrap()
+    }
+
+    fn bucket_mut(&mut self) -> &mut Bucket<K, V> {
+        self.table.slot_mut(self.index).bucket.as_mut().unwrap()
+    }
+}
+
+impl<'a, K, V, S> OccupiedEntry<'a> for Occupied<'a, K, V, S>
+where
+    K: PartialEq + Hash + 'a,
+    V: 'a,
+    S: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.bucket().key
+    }
+
+    fn get(&self) -> &V {
+        &self.bucket().value
+    }
+
+    fn get_mut(&mut self) -> &mut V {
+        &mut self.bucket_mut().value
+    }
+
+    fn into_mut(self) -> &'a mut V {
+        let table = self.table;
+
+        &mut table.slot_mut(self.index).bucket.as_mut().unwrap().value
+    }
+
+    fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    fn remove(self) -> V {
+        let bucket = self.table.take(self.index);
+        self.table.shrink_if_sparse();
+
+        bucket.value
+    }
+}
+
+pub struct Vacant<'a, K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    pub(crate) table: &'a mut HopscotchHashTable<K, V, S>,
+    pub(crate) key: K,
+    pub(crate) hash: u64,
+}
+
+impl<'a, K, V, S> VacantEntry<'a> for Vacant<'a, K, V, S>
+where
+    K: PartialEq + Hash + 'a,
+    V: 'a,
+    S: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn into_key(self) -> K {
+        self.key
+    }
+
+    fn insert(self, value: V) -> &'a mut V {
+        let table = self.table;
+        table.count += 1;
+
+        // the displacement may move the other buckets, but not the inserted one
+        let index = table.insert_bucket(Bucket {
+            key: self.key,
+            hash: self.hash,
+            value,
+        });
+
+        &mut table.slot_mut(index).bucket.as_mut().unwrap().value
+    }
+}
@@ -0,0 +1,135 @@
+use std::hash::{BuildHasher, Hash};
+use std::slice;
+
+use crate::RawHashTable;
+
+use super::{HopscotchHashTable, Slot};
+
+/// Iterator over the slots holding a bucket.
+pub struct Iter<'a, K, V> {
+    slots: slice::Iter<'a, Slot<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    pub(crate) fn new(table: &'a RawHashTable) -> Self {
+        let slots = unsafe {
+            slice::from_raw_parts(table.buckets.as_ptr() as *const Slot<K, V>, table.mask + 1)
+        };
+
+        Self {
+            slots: slots.iter(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.find_map(|slot| {
+            let bucket = slot.bucket.as_ref()?;
+            Some((&bucket.key, &bucket.value))
+        })
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    slots: slice::IterMut<'a, Slot<K, V>>,
+}
+
+impl<'a, K, V> IterMut<'a, K, V> {
+    pub(crate) fn new(table: &'a mut RawHashTable) -> Self {
+        let slots = unsafe {
+            slice::from_raw_parts_mut(table.buckets.as_ptr() as *mut Slot<K, V>, table.mask + 1)
+        };
+
+        Self {
+            slots: slots.iter_mut(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.find_map(|slot| {
+            let bucket = slot.bucket.as_mut()?;
+            Some((&bucket.key, &mut bucket.value))
+        })
+    }
+}
+
+/// Owning iterator, taking out the buckets from the table. The table still owns the slot array
+/// and frees it on drop.
+pub struct IntoIter<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    table: HopscotchHashTable<K, V, S>,
+    index: usize,
+}
+
+impl<K, V, S> Iterator for IntoIter<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index <= self.table.inner.mask {
+            let slot = self.table.slot_mut(self.index);
+            self.index += 1;
+
+            if let Some(bucket) = slot.bucket.take() {
+                return Some((bucket.key, bucket.value));
+            }
+        }
+
+        None
+    }
+}
+
+impl<K, V, S> IntoIterator for HopscotchHashTable<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            table: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a HopscotchHashTable<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(&self.inner)
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut HopscotchHashTable<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IterMut::new(&mut self.inner)
+    }
+}
@@ -0,0 +1,479 @@
+use std::alloc::{alloc, dealloc, Layout};
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, BuildHasherDefault};
+use std::marker::PhantomData;
+use std::{hash::Hash, ptr::NonNull};
+use std::{mem, ptr};
+
+use crate::{
+    size_for, Entry, HashMap, OccupiedEntry, RawHashTable, VacantEntry, INITIAL_SIZE, LOAD_FACTOR,
+};
+
+mod entry;
+mod iter;
+mod set;
+
+pub use entry::{Occupied, Vacant};
+pub use iter::{IntoIter, Iter, IterMut};
+pub use set::HopscotchHashSet;
+
+// the default size of the neighborhood, where the bucket should be placed from its home
+pub const NEIGHBORHOOD: usize = 32;
+
+pub struct Bucket<K, V> {
+    key: K,
+    hash: u64,
+    value: V,
+}
+
+struct Slot<K, V> {
+    // the bit `d` is set if the bucket homed here is placed on `d` slots away
+    hop_info: u64,
+    bucket: Option<Bucket<K, V>>,
+}
+
+fn alloc_slots<K, V>(size: usize) -> NonNull<u8> {
+    let layout = match Layout::array::<Slot<K, V>>(size) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Cannot initialize Slots"),
+    };
+
+    // allocate and init with empty slots
+    unsafe {
+        let raw = alloc(layout) as *mut Slot<K, V>;
+
+        for i in 0..size {
+            ptr::write(
+                raw.add(i),
+                Slot {
+                    hop_info: 0,
+                    bucket: None,
+                },
+            );
+        }
+
+        NonNull::new(raw as *mut u8).unwrap()
+    }
+}
+
+fn dealloc_slots<K, V>(ptr: NonNull<u8>, size: usize) {
+    let layout = match Layout::array::<Slot<K, V>>(size) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Cannot initialize Slots"),
+    };
+
+    unsafe {
+        dealloc(ptr.as_ptr(), layout);
+    }
+}
+
+/// Hopscotch hashing keeps every bucket in the neighborhood of `H` slots from its home, and each
+/// home has the bitmap of its buckets. So the lookup only visits the buckets homed on the same
+/// slot. If there is no empty slot in the neighborhood, the empty slot found by linear probing
+/// hops back by displacing the buckets, and the table is resized when it cannot.
+pub struct HopscotchHashTable<K, V, S = BuildHasherDefault<DefaultHasher>>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    hasher: S,
+    inner: RawHashTable,
+    count: usize,
+    neighborhood: usize,
+    load_factor: f32,
+    shrink_load_factor: f32, // 0.0 if the table does not shrink automatically
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K: PartialEq + Hash, V, S: BuildHasher> Drop for HopscotchHashTable<K, V, S> {
+    fn drop(&mut self) {
+        let first_slot = self.inner.buckets.as_ptr() as *mut Slot<K, V>;
+
+        for index in 0..=self.inner.mask {
+            unsafe { ptr::drop_in_place(first_slot.add(index)) };
+        }
+
+        dealloc_slots::<K, V>(self.inner.buckets, self.inner.mask + 1);
+    }
+}
+
+impl<K, V, S> HopscotchHashTable<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    /// `neighborhood` is the size H of the neighborhood, which is up to 64 for the bitmap.
+    pub fn new_with_properties(
+        hasher: S,
+        neighborhood: usize,
+        initial_size: usize,
+        load_factor: f32,
+    ) -> Self {
+        assert!(
+            (1..=64).contains(&neighborhood),
+            "The neighborhood should be in 1..=64."
+        );
+
+        Self {
+            hasher,
+            inner: RawHashTable {
+                buckets: alloc_slots::<K, V>(initial_size),
+                mask: initial_size - 1,
+            },
+            count: 0,
+            neighborhood,
+            load_factor,
+            shrink_load_factor: 0.0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Shrink the table automatically when the load drops under `shrink_load_factor` by
+    /// removals. It is disabled by default(`0.0`), and `SHRINK_LOAD_FACTOR` is a reasonable value.
+    pub fn set_shrink_load_factor(&mut self, shrink_load_factor: f32) {
+        self.shrink_load_factor = shrink_load_factor;
+    }
+
+    fn hash_one<Q: ?Sized + Hash>(&self, key: &Q) -> u64 {
+        self.hasher.hash_one(key)
+    }
+
+    // the neighborhood on the small table wraps around, so it is limited by the size
+    fn hop_range(&self) -> usize {
+        self.neighborhood.min(self.inner.mask + 1)
+    }
+
+    fn slot(&self, index: usize) -> &Slot<K, V> {
+        let first_slot = self.inner.buckets.as_ptr() as *const Slot<K, V>;
+
+        unsafe { &*first_slot.add(index & self.inner.mask) }
+    }
+
+    fn slot_mut(&mut self, index: usize) -> &mut Slot<K, V> {
+        let first_slot = self.inner.buckets.as_ptr() as *mut Slot<K, V>;
+
+        unsafe { &mut *first_slot.add(index & self.inner.mask) }
+    }
+
+    // find the index of the key by the bitmap of its home
+    fn find<Q>(&self, key: &Q, hash: u64) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let home = hash as usize & self.inner.mask;
+        let mut hop_info = self.slot(home).hop_info;
+
+        while hop_info != 0 {
+            let index = (home + hop_info.trailing_zeros() as usize) & self.inner.mask;
+
+            if let Some(bucket) = &self.slot(index).bucket {
+                if bucket.hash == hash && bucket.key.borrow() == key {
+                    return Some(index);
+                }
+            }
+
+            hop_info &= hop_info - 1;
+        }
+
+        None
+    }
+
+    // place the bucket known not to be on the table, and return the index where it is placed
+    fn place(&mut self, bucket: Bucket<K, V>) -> Result<usize, Bucket<K, V>> {
+        let mask = self.inner.mask;
+        let hop_range = self.hop_range();
+        let home = bucket.hash as usize & mask;
+
+        let mut free = match (0..=mask)
+            .map(|distance| (home + distance) & mask)
+            .find(|index| self.slot(*index).bucket.is_none())
+        {
+            Some(free) => free,
+            None => return Err(bucket),
+        };
+
+        // hop the empty slot back into the neighborhood of the home
+        while free.wrapping_sub(home) & mask >= hop_range {
+            let mut moved = false;
+
+            // the farthest home first, whose buckets can be moved to the empty slot
+            for back in (1..hop_range).rev() {
+                let candidate = free.wrapping_sub(back) & mask;
+                let hop_info = self.slot(candidate).hop_info & ((1 << back) - 1);
+
+                if hop_info == 0 {
+                    continue;
+                }
+
+                let distance = hop_info.trailing_zeros() as usize;
+                let index = (candidate + distance) & mask;
+
+                let hopped = self.slot_mut(index).bucket.take();
+                self.slot_mut(free).bucket = hopped;
+                self.slot_mut(candidate).hop_info ^= (1 << distance) | (1 << back);
+
+                free = index;
+                moved = true;
+                break;
+            }
+
+            if !moved {
+                return Err(bucket);
+            }
+        }
+
+        self.slot_mut(home).hop_info |= 1 << (free.wrapping_sub(home) & mask);
+        self.slot_mut(free).bucket = Some(bucket);
+
+        Ok(free)
+    }
+
+    // place the bucket, and resize the table until the neighborhood has the room
+    fn insert_bucket(&mut self, mut bucket: Bucket<K, V>) -> usize {
+        loop {
+            match self.place(bucket) {
+                Ok(index) => return index,
+                Err(failed) => {
+                    bucket = failed;
+                    self.resize((self.inner.mask + 1) << 1);
+                }
+            }
+        }
+    }
+
+    fn take(&mut self, index: usize) -> Bucket<K, V> {
+        let bucket = self.slot_mut(index).bucket.take().unwrap();
+        let home = bucket.hash as usize & self.inner.mask;
+
+        self.slot_mut(home).hop_info &= !(1 << (index.wrapping_sub(home) & self.inner.mask));
+        self.count -= 1;
+
+        bucket
+    }
+
+    fn reserve_one(&mut self) {
+        if self.count >= self.capacity_of(self.inner.mask + 1) {
+            self.resize((self.inner.mask + 1) << 1);
+        }
+    }
+
+    fn capacity_of(&self, size: usize) -> usize {
+        (size as f32 * self.load_factor) as usize
+    }
+
+    // insert the bucket unless its key exists, and give the bucket back otherwise
+    fn insert_new(&mut self, bucket: Bucket<K, V>) -> Result<(), Bucket<K, V>> {
+        if self.find(&bucket.key, bucket.hash).is_some() {
+            return Err(bucket);
+        }
+
+        self.reserve_one();
+        self.insert_bucket(bucket);
+        self.count += 1;
+
+        Ok(())
+    }
+
+    // remove the bucket of the key, which the map and the set take the value or the key from
+    fn remove_bucket<Q>(&mut self, key: &Q) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let index = self.find(key, self.hash_one(key)).ok_or(())?;
+        let bucket = self.take(index);
+
+        self.shrink_if_sparse();
+
+        Ok(bucket)
+    }
+
+    // shrink the table to the half load of the load factor if it gets sparse
+    fn shrink_if_sparse(&mut self) {
+        let size = self.inner.mask + 1;
+
+        if size > INITIAL_SIZE && (self.count as f32) < size as f32 * self.shrink_load_factor {
+            self.resize(size_for(self.count * 2, self.load_factor));
+        }
+    }
+
+    fn resize(&mut self, mut new_size: usize) {
+        let mut pending = Vec::with_capacity(self.count);
+
+        loop {
+            let new_inner = RawHashTable {
+                buckets: alloc_slots::<K, V>(new_size),
+                mask: new_size - 1,
+            };
+
+            let old_inner = mem::replace(&mut self.inner, new_inner);
+            let old_slot = old_inner.buckets.as_ptr() as *mut Slot<K, V>;
+
+            for index in 0..=old_inner.mask {
+                let slot = unsafe { ptr::read(old_slot.add(index)) };
+                pending.extend(slot.bucket);
+            }
+
+            dealloc_slots::<K, V>(old_inner.buckets, old_inner.mask + 1);
+
+            while let Some(bucket) = pending.pop() {
+                if let Err(bucket) = self.place(bucket) {
+                    // the neighborhood overflows even on the new table, so grow it again
+                    pending.push(bucket);
+                    break;
+                }
+            }
+
+            if pending.is_empty() {
+                return;
+            }
+
+            new_size <<= 1;
+        }
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S> for HopscotchHashTable<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self::new_with_properties(S::default(), NEIGHBORHOOD, INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    fn with_hasher(hasher: S) -> Self {
+        Self::new_with_properties(hasher, NEIGHBORHOOD, INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::new_with_properties(
+            S::default(),
+            NEIGHBORHOOD,
+            size_for(capacity, LOAD_FACTOR),
+            LOAD_FACTOR,
+        )
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Result<(), V> {
+        let hash = self.hash_one(&key);
+
+        self.insert_new(Bucket { key, hash, value })
+            .map_err(|bucket| bucket.value)
+    }
+
+    fn lookup<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let index = self.find(key, self.hash_one(key))?;
+
+        self.slot(index).bucket.as_ref().map(|bucket| &bucket.value)
+    }
+
+    fn lookup_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let index = self.find(key, self.hash_one(key))?;
+
+        self.slot_mut(index)
+            .bucket
+            .as_mut()
+            .map(|bucket| &mut bucket.value)
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        self.remove_bucket(key).map(|bucket| bucket.value)
+    }
+
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity_of(self.inner.mask + 1)
+    }
+
+    fn tombstones(&self) -> usize {
+        // the removed bucket only clears its bit on the bitmap of the home
+        0
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        if self.count + additional > self.capacity() {
+            let new_size = size_for(self.count + additional, self.load_factor);
+            self.resize(new_size);
+        }
+    }
+
+    fn shrink_to(&mut self, min_capacity: usize) {
+        let new_size = size_for(self.count.max(min_capacity), self.load_factor);
+
+        if new_size < self.inner.mask + 1 {
+            self.resize(new_size);
+        }
+    }
+
+    fn insert_or_replace(&mut self, key: K, value: V) -> Option<V> {
+        self.entry(key).insert(value)
+    }
+
+    fn clear(&mut self) {
+        for index in 0..=self.inner.mask {
+            let slot = self.slot_mut(index);
+
+            slot.hop_info = 0;
+            slot.bucket = None;
+        }
+
+        self.count = 0;
+    }
+
+    fn entry<'a>(
+        &'a mut self,
+        key: K,
+    ) -> Entry<impl OccupiedEntry<'a, Key = K, Value = V>, impl VacantEntry<'a, Key = K, Value = V>>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let hash = self.hash_one(&key);
+
+        if let Some(index) = self.find(&key, hash) {
+            Entry::Occupied(Occupied { table: self, index })
+        } else {
+            self.reserve_one();
+
+            Entry::Vacant(Vacant {
+                table: self,
+                key,
+                hash,
+            })
+        }
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        Iter::new(&self.inner)
+    }
+
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (&'a K, &'a mut V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        IterMut::new(&mut self.inner)
+    }
+}
@@ -0,0 +1,148 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
+use std::iter::{FromIterator, Map};
+
+use crate::{HashMap, HashSet};
+
+use super::{Bucket, HopscotchHashTable, IntoIter, Iter};
+
+/// The set on `HopscotchHashTable`, whose buckets hold the keys only since the value is `()`.
+pub struct HopscotchHashSet<K, S = BuildHasherDefault<DefaultHasher>>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    table: HopscotchHashTable<K, (), S>,
+}
+
+impl<K, S> HopscotchHashSet<K, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    pub fn new_with_properties(
+        hasher: S,
+        neighborhood: usize,
+        initial_size: usize,
+        load_factor: f32,
+    ) -> Self {
+        Self {
+            table: HopscotchHashTable::new_with_properties(
+                hasher,
+                neighborhood,
+                initial_size,
+                load_factor,
+            ),
+        }
+    }
+}
+
+impl<K, S> HashSet<K, S> for HopscotchHashSet<K, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self {
+            table: HopscotchHashTable::new(),
+        }
+    }
+
+    fn with_hasher(hasher: S) -> Self {
+        Self {
+            table: HopscotchHashTable::with_hasher(hasher),
+        }
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            table: HopscotchHashTable::with_capacity(capacity),
+        }
+    }
+
+    fn insert(&mut self, key: K) -> Result<(), K> {
+        let hash = self.table.hash_one(&key);
+        let bucket = Bucket {
+            key,
+            hash,
+            value: (),
+        };
+
+        self.table.insert_new(bucket).map_err(|bucket| bucket.key)
+    }
+
+    fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        self.table.lookup(key).is_some()
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<K, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        self.table.remove_bucket(key).map(|bucket| bucket.key)
+    }
+
+    fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    fn clear(&mut self) {
+        self.table.clear();
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a K>
+    where
+        K: 'a,
+    {
+        self.into_iter()
+    }
+}
+
+impl<K, S> FromIterator<K> for HopscotchHashSet<K, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher + Default,
+{
+    // the duplicated keys are dropped
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = Self::new();
+
+        for key in iter {
+            let _ = set.insert(key);
+        }
+
+        set
+    }
+}
+
+impl<K, S> IntoIterator for HopscotchHashSet<K, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    type Item = K;
+    type IntoIter = Map<IntoIter<K, (), S>, fn((K, ())) -> K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.table.into_iter().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K, S> IntoIterator for &'a HopscotchHashSet<K, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    type Item = &'a K;
+    type IntoIter = Map<Iter<'a, K, ()>, fn((&'a K, &'a ())) -> &'a K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (&self.table).into_iter().map(|(key, _)| key)
+    }
+}
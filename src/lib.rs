@@ -1,32 +1,76 @@
 use std::{
-    hash::{BuildHasher, Hash, BuildHasherDefault},
+    borrow::Borrow,
+    collections::hash_map::DefaultHasher,
+    hash::{BuildHasher, BuildHasherDefault, Hash},
     marker::PhantomData,
-    ptr::NonNull, collections::hash_map::DefaultHasher,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
 };
 
+// count on the metrics of the thread, which is compiled out without the `metrics` feature
+macro_rules! metric {
+    ($counter:ident) => {
+        metric!($counter, 1)
+    };
+    ($counter:ident, $n:expr) => {{
+        #[cfg(feature = "metrics")]
+        $crate::metrics::add(|metrics| metrics.$counter += $n as u64);
+    }};
+}
+
+mod entry;
+
 pub mod chaining;
+pub mod cuckoo;
+pub mod hopscotch;
+pub mod lock_free;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod open_addressing;
+pub mod sharded;
+pub mod swiss;
+
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
 
 pub const INITIAL_SIZE: usize = 8;
 pub const LOAD_FACTOR: f32 = 0.7;
+// the low-water load factor to shrink the table after removals, which is opt-in
+pub const SHRINK_LOAD_FACTOR: f32 = 0.1;
+// the ratio of the tombstones on the table to purge them in place, which is opt-in
+pub const TOMBSTONE_FACTOR: f32 = 0.2;
 
 pub struct RawHashTable {
     buckets: NonNull<u8>,
     mask: usize,
 }
 
-struct HashTable<K: Hash + PartialEq, V, S: BuildHasher, E: Entry<K, B>, B> {
+struct HashTable<K: Hash + PartialEq, V, S: BuildHasher, E: EntryStrategy<K, B>, B> {
     hasher: S,
     inner: RawHashTable,
-    count: usize,
+    count: usize,      // the number of live buckets
+    tombstones: usize, // the number of tombstones, which also occupy the table
     load_factor: f32,
+    shrink_load_factor: f32, // 0.0 if the table does not shrink automatically
+    tombstone_factor: f32,   // 1.0 if the tombstones are not purged automatically
     entry: Box<E>,
     _marker: PhantomData<(K, V, E, B)>,
 }
 
-pub enum EntryResult<T> {
-    None(NonNull<T>),
-    Some(NonNull<T>),
+// the smallest power-of-two size from INITIAL_SIZE, which holds `capacity` entries
+fn size_for(capacity: usize, load_factor: f32) -> usize {
+    let mut size = INITIAL_SIZE;
+
+    while ((size as f32 * load_factor) as usize) < capacity {
+        size = size.checked_mul(2).expect("capacity overflow");
+    }
+
+    size
+}
+
+// the index of the bucket on the table
+pub enum EntryResult {
+    None(usize), // the key does not exist, and the index is where it should be inserted
+    Some(usize),
     Full, // the all of available access entries are full(Some or Tombstone)
 }
 
@@ -37,16 +81,279 @@ pub enum InsertResult<T> {
     Full(T),
 }
 
-pub trait Entry<K: PartialEq, B> : Default {
-    fn insert(&mut self, table: &RawHashTable, bucket: B) -> InsertResult<B>;
-    fn lookup<'a>(&self, table: &'a RawHashTable, key: &K, hash: u64) -> Option<&'a B>;
-    fn remove(&mut self, table: &RawHashTable, key: &K, hash: u64) -> Result<B, ()>;
+pub trait EntryStrategy<K: PartialEq, B>: Default {
+    fn insert(&mut self, table: &mut RawHashTable, bucket: B) -> InsertResult<B>;
+    // the lookup only reads the buckets by shared references, so it may run concurrently
+    fn lookup<'a, Q>(&self, table: &'a RawHashTable, key: &Q, hash: u64) -> Option<&'a B>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq;
+    fn lookup_mut<'a, Q>(
+        &self,
+        table: &'a mut RawHashTable,
+        key: &Q,
+        hash: u64,
+    ) -> Option<&'a mut B>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq;
+    #[allow(clippy::result_unit_err)]
+    fn remove<Q>(&mut self, table: &mut RawHashTable, key: &Q, hash: u64) -> Result<B, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq;
+
+    // find the key by one walk, for the entry API
+    fn entry<Q>(&self, table: &RawHashTable, key: &Q, hash: u64) -> EntryResult
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq;
+    // insert the bucket on `EntryResult::None(index)`, and return the index where it is placed and
+    // whether a tombstone is taken, which may be other than the one at `index`(ex. LCFS). The
+    // bucket is given back if it cannot be placed without a resize.
+    fn insert_at(
+        &mut self,
+        table: &mut RawHashTable,
+        index: usize,
+        bucket: B,
+    ) -> Result<(usize, bool), B>;
+    // remove the bucket on `EntryResult::Some(index)`
+    fn remove_at(&mut self, table: &mut RawHashTable, index: usize) -> B;
+    // replace all tombstones by None, placing the buckets again to keep them reachable
+    fn rehash_in_place(&mut self, table: &mut RawHashTable);
+
+    // the number of the buckets visited by the lookup of the bucket on `index`
+    fn probe_length(&self, table: &RawHashTable, index: usize) -> usize;
+    // the number of the buckets visited by the lookup of an absent key, whose home is `home`,
+    // which is exact only if the probe sequence is drawn from the home alone
+    fn miss_length(&self, table: &RawHashTable, home: usize) -> usize;
 }
 
 pub trait HashMap<K, V, S = BuildHasherDefault<DefaultHasher>> {
     fn new() -> Self;
     fn with_hasher(hasher: S) -> Self;
-    fn insert(&mut self, key: &K, value: V) -> Result<(), V>;
-    fn lookup(&self, key: &K) -> Option<&V>;
-    fn remove(&mut self, key: &K) -> Result<V, ()>;
+    fn with_capacity(capacity: usize) -> Self;
+    fn insert(&mut self, key: K, value: V) -> Result<(), V>;
+
+    #[deprecated(note = "`insert` takes the owned key, so clone the key on the call site")]
+    fn insert_cloned(&mut self, key: &K, value: V) -> Result<(), V>
+    where
+        K: Clone,
+    {
+        self.insert(key.clone(), value)
+    }
+
+    // the key is looked up by any borrowed form of it, whose hash and equality should be the same
+    fn lookup<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq;
+    fn lookup_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq;
+    #[allow(clippy::result_unit_err)]
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq;
+
+    fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        self.lookup(key).is_some()
+    }
+
+    fn len(&self) -> usize;
+    // the number of entries that can be held without resizing
+    fn capacity(&self) -> usize;
+    // the number of removed buckets still occupying the table
+    fn tombstones(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // make room for `additional` more entries, so that they are inserted without resizing
+    fn reserve(&mut self, additional: usize);
+    // shrink the table as much as possible, but keep room for `min_capacity` entries
+    fn shrink_to(&mut self, min_capacity: usize);
+
+    fn shrink_to_fit(&mut self) {
+        self.shrink_to(0);
+    }
+
+    // insert the value even if the key exists, and return the replaced value
+    fn insert_or_replace(&mut self, key: K, value: V) -> Option<V>;
+
+    #[deprecated(
+        note = "`insert_or_replace` takes the owned key, so clone the key on the call site"
+    )]
+    fn insert_or_replace_cloned(&mut self, key: &K, value: V) -> Option<V>
+    where
+        K: Clone,
+    {
+        self.insert_or_replace(key.clone(), value)
+    }
+
+    // remove all entries, but keep the allocated table
+    fn clear(&mut self);
+
+    fn entry<'a>(
+        &'a mut self,
+        key: K,
+    ) -> Entry<impl OccupiedEntry<'a, Key = K, Value = V>, impl VacantEntry<'a, Key = K, Value = V>>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (&'a K, &'a mut V)>
+    where
+        K: 'a,
+        V: 'a;
+
+    fn keys<'a>(&'a self) -> impl Iterator<Item = &'a K>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.iter().map(|(key, _)| key)
+    }
+
+    fn values<'a>(&'a self) -> impl Iterator<Item = &'a V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.iter().map(|(_, value)| value)
+    }
+
+    fn values_mut<'a>(&'a mut self) -> impl Iterator<Item = &'a mut V>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        self.iter_mut().map(|(_, value)| value)
+    }
+}
+
+/// The set of keys, whose set algebra walks `self` and looks up the keys on `other`.
+pub trait HashSet<K, S = BuildHasherDefault<DefaultHasher>> {
+    fn new() -> Self;
+    fn with_hasher(hasher: S) -> Self;
+    fn with_capacity(capacity: usize) -> Self;
+    // give the key back if it already exists
+    fn insert(&mut self, key: K) -> Result<(), K>;
+    fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq;
+    #[allow(clippy::result_unit_err)]
+    fn remove<Q>(&mut self, key: &Q) -> Result<K, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // remove all keys, but keep the allocated table
+    fn clear(&mut self);
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a K>
+    where
+        K: 'a;
+
+    fn union<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a K>
+    where
+        K: 'a + Hash + PartialEq,
+    {
+        self.iter().chain(other.difference(self))
+    }
+
+    fn intersection<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a K>
+    where
+        K: 'a + Hash + PartialEq,
+    {
+        self.iter().filter(move |key| other.contains(*key))
+    }
+
+    fn difference<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a K>
+    where
+        K: 'a + Hash + PartialEq,
+    {
+        self.iter().filter(move |key| !other.contains(*key))
+    }
+
+    fn symmetric_difference<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = &'a K>
+    where
+        K: 'a + Hash + PartialEq,
+    {
+        self.difference(other).chain(other.difference(self))
+    }
+
+    fn is_subset(&self, other: &Self) -> bool
+    where
+        K: Hash + PartialEq,
+    {
+        self.len() <= other.len() && self.iter().all(|key| other.contains(key))
+    }
+}
+
+/// The map shared across threads, whose operations take `&self`. The value is reached through
+/// the guard, which holds the lock of the part of the map having the key, or keeps the value
+/// from being reclaimed on the lock-free map.
+pub trait ConcurrentHashMap<K, V, S = BuildHasherDefault<DefaultHasher>>: Send + Sync {
+    type Guard<'a>: Deref<Target = V>
+    where
+        Self: 'a;
+
+    fn new() -> Self;
+    fn with_hasher(hasher: S) -> Self;
+    fn insert(&self, key: K, value: V) -> Result<(), V>;
+    fn lookup(&self, key: &K) -> Option<Self::Guard<'_>>;
+    #[allow(clippy::result_unit_err)]
+    fn remove(&self, key: &K) -> Result<V, ()>;
+
+    // insert the value even if the key exists, and return the replaced value
+    fn insert_or_replace(&self, key: K, value: V) -> Option<V>;
+
+    // the sum of the parts, which may be changing by the other threads
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn clear(&self);
+
+    // clone the value not to hold the lock
+    fn lookup_cloned(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lookup(key).map(|value| value.clone())
+    }
+}
+
+/// The concurrent map whose value can be updated in place, since the guard holds the lock of the
+/// part of the map having the key.
+pub trait ConcurrentHashMapMut<K, V, S = BuildHasherDefault<DefaultHasher>>:
+    ConcurrentHashMap<K, V, S>
+{
+    type GuardMut<'a>: DerefMut<Target = V>
+    where
+        Self: 'a;
+
+    fn lookup_mut(&self, key: &K) -> Option<Self::GuardMut<'_>>;
 }
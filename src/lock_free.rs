@@ -0,0 +1,730 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ptr::{self, NonNull};
+
+#[cfg(not(loom))]
+use crossbeam_epoch::{pin, Guard};
+
+use self::sync::{AtomicBool, AtomicIsize, AtomicPtr, AtomicUsize, Ordering};
+use crate::{ConcurrentHashMap, INITIAL_SIZE, LOAD_FACTOR};
+
+// the atomics are replaced by loom, which checks the interleavings of them
+mod sync {
+    #[cfg(loom)]
+    pub(crate) use loom::sync::atomic::{
+        AtomicBool, AtomicIsize, AtomicPtr, AtomicUsize, Ordering,
+    };
+    #[cfg(loom)]
+    pub(crate) use loom::sync::Mutex;
+    #[cfg(not(loom))]
+    pub(crate) use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicPtr, AtomicUsize, Ordering};
+}
+
+// loom does not know the epochs, so the garbage is kept until the map is dropped
+#[cfg(loom)]
+struct Guard;
+
+#[cfg(loom)]
+fn pin() -> Guard {
+    Guard
+}
+
+// the number of the slots migrated by a thread at once
+const CHUNK: usize = 64;
+
+// the empty key slot is MOVED when the slot is being migrated, not to be claimed anymore
+const MOVED: usize = 1;
+// the value is FROZEN on the low bit when the slot is being migrated, not to be updated anymore
+const FROZEN: usize = 1;
+// the removed value, whose key stays on the slot
+const REMOVED: usize = 2;
+
+fn marker<T>(addr: usize) -> *mut T {
+    ptr::null_mut::<T>().with_addr(addr)
+}
+
+fn is_frozen<T>(ptr: *mut T) -> bool {
+    ptr.addr() & FROZEN != 0
+}
+
+fn freeze<T>(ptr: *mut T) -> *mut T {
+    ptr.map_addr(|addr| addr | FROZEN)
+}
+
+fn unfrozen<T>(ptr: *mut T) -> *mut T {
+    ptr.map_addr(|addr| addr & !FROZEN)
+}
+
+// the value which is neither null nor removed
+fn live<T>(ptr: *mut T) -> Option<NonNull<T>> {
+    let ptr = unfrozen(ptr);
+
+    if ptr.addr() == REMOVED {
+        None
+    } else {
+        NonNull::new(ptr)
+    }
+}
+
+// the pointer and how to drop it
+#[cfg(loom)]
+type Garbage = (*mut u8, unsafe fn(*mut u8));
+
+#[cfg(loom)]
+unsafe fn drop_box<T>(ptr: *mut u8) {
+    drop(Box::from_raw(ptr as *mut T));
+}
+
+// the key is set once on the slot, and it is never removed until the table is dropped
+struct Key<K> {
+    key: K,
+    hash: u64,
+    // The number of the tables owning the key. The removed key is left on the old table by the
+    // migration, and the thread which claimed it may claim a slot of the next table with it again.
+    tables: AtomicUsize,
+}
+
+impl<K> Key<K> {
+    // drop the key if the table is the last one owning it
+    unsafe fn release(key: *mut Self) {
+        if (*key).tables.fetch_sub(1, Ordering::AcqRel) == 1 {
+            drop(Box::from_raw(key));
+        }
+    }
+}
+
+// the key to claim an empty slot with, which is dropped unless a table takes it
+struct NewKey<K> {
+    key: *mut Key<K>,
+    claimed: bool,
+}
+
+impl<K> NewKey<K> {
+    fn new(key: K, hash: u64) -> Self {
+        Self {
+            key: Box::into_raw(Box::new(Key {
+                key,
+                hash,
+                tables: AtomicUsize::new(0),
+            })),
+            claimed: false,
+        }
+    }
+}
+
+impl<K> Drop for NewKey<K> {
+    fn drop(&mut self) {
+        if !self.claimed {
+            drop(unsafe { Box::from_raw(self.key) });
+        }
+    }
+}
+
+#[repr(align(4))]
+struct Value<V>(V);
+
+struct Slot<K, V> {
+    key: AtomicPtr<Key<K>>,
+    value: AtomicPtr<Value<V>>, // null until the value is set after the key
+}
+
+struct Table<K, V> {
+    slots: Box<[Slot<K, V>]>,
+    capacity: usize,
+    used: AtomicUsize, // the number of the key slots, including the removed keys
+    next: AtomicPtr<Table<K, V>>, // the table being migrated to
+    claimed: AtomicUsize, // the next chunk to migrate
+    done: Box<[AtomicBool]>, // whether each chunk is migrated
+}
+
+impl<K, V> Table<K, V> {
+    fn new(size: usize, load_factor: f32) -> Self {
+        assert!(
+            size.is_power_of_two(),
+            "The size of the table should be a power of two."
+        );
+
+        Self {
+            slots: (0..size)
+                .map(|_| Slot {
+                    key: AtomicPtr::new(ptr::null_mut()),
+                    value: AtomicPtr::new(ptr::null_mut()),
+                })
+                .collect(),
+            // an empty slot is left at least, which stops the probe
+            capacity: ((size as f32 * load_factor) as usize).min(size - 1),
+            used: AtomicUsize::new(0),
+            next: AtomicPtr::new(ptr::null_mut()),
+            claimed: AtomicUsize::new(0),
+            done: (0..size.div_ceil(CHUNK))
+                .map(|_| AtomicBool::new(false))
+                .collect(),
+        }
+    }
+
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+}
+
+impl<K, V> Drop for Table<K, V> {
+    fn drop(&mut self) {
+        for slot in self.slots.iter() {
+            let key = slot.key.load(Ordering::Relaxed);
+            let value = slot.value.load(Ordering::Relaxed);
+
+            if key.is_null() || key == marker(MOVED) {
+                continue;
+            }
+
+            // the frozen key is owned by the next table, unless it is removed
+            if is_frozen(value) && unfrozen(value) != marker(REMOVED) {
+                continue;
+            }
+
+            unsafe {
+                Key::release(key);
+
+                if let Some(value) = live(value) {
+                    drop(Box::from_raw(value.as_ptr()));
+                }
+            }
+        }
+    }
+}
+
+enum Probe<'a, K, V> {
+    Found(&'a Slot<K, V>),
+    Absent,
+    Moved, // the table is being migrated
+    Full,  // the table should be migrated to claim a new key
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Update {
+    Insert,
+    Replace,
+    Remove,
+}
+
+/// The lock-free map on the linear probing, which is the folklore table of Maier, Sanders and
+/// Dementiev. A key is set once on the slot by CAS, and the slot is never reused for another key.
+/// The updates replace the value by CAS, and the removed key keeps the REMOVED value until the
+/// table is migrated.
+///
+/// The table is migrated to the new one cooperatively: the threads claim the chunks of the
+/// slots, freeze them and copy the live ones, and the updates help the migration before going on.
+/// The migration of a slot is idempotent, so the threads redo the chunks not done yet instead of
+/// waiting for the threads which claimed them, and the updates stay lock-free while migrating.
+/// The removed values and the old tables are reclaimed by the epochs.
+pub struct LockFreeHashMap<K, V, S = BuildHasherDefault<DefaultHasher>> {
+    hasher: S,
+    table: AtomicPtr<Table<K, V>>,
+    len: AtomicIsize, // it can be negative for a while, by the racing insert and remove
+    load_factor: f32,
+    #[cfg(loom)]
+    garbage: sync::Mutex<Vec<Garbage>>,
+    _marker: PhantomData<(Box<K>, Box<V>)>,
+}
+
+unsafe impl<K: Send + Sync, V: Send + Sync, S: Send> Send for LockFreeHashMap<K, V, S> {}
+unsafe impl<K: Send + Sync, V: Send + Sync, S: Sync> Sync for LockFreeHashMap<K, V, S> {}
+
+/// The guard of the value, which keeps the thread pinned not to reclaim the value.
+pub struct Ref<'a, V> {
+    _guard: Guard,
+    value: NonNull<V>,
+    _marker: PhantomData<&'a V>,
+}
+
+impl<'a, V> Deref for Ref<'a, V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<K, V, S> Default for LockFreeHashMap<K, V, S>
+where
+    K: PartialEq + Hash + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Default + Send + Sync,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, S> LockFreeHashMap<K, V, S>
+where
+    K: PartialEq + Hash + Send + 'static,
+    V: Send + 'static,
+    S: BuildHasher,
+{
+    pub fn new_with_properties(hasher: S, initial_size: usize, load_factor: f32) -> Self {
+        Self {
+            hasher,
+            table: AtomicPtr::new(Box::into_raw(Box::new(Table::new(
+                initial_size,
+                load_factor,
+            )))),
+            len: AtomicIsize::new(0),
+            load_factor,
+            #[cfg(loom)]
+            garbage: sync::Mutex::new(Vec::new()),
+            _marker: PhantomData,
+        }
+    }
+
+    // find the slot of the key, or claim an empty slot for it with `claim`
+    fn probe<'a>(
+        &self,
+        table: &'a Table<K, V>,
+        key: &K,
+        hash: u64,
+        mut claim: Option<&mut NewKey<K>>,
+    ) -> Probe<'a, K, V> {
+        let mut index = hash as usize & table.mask();
+
+        for _ in 0..table.slots.len() {
+            let slot = &table.slots[index];
+            let mut current = slot.key.load(Ordering::Acquire);
+
+            if current.is_null() {
+                let new_key = match claim.as_deref_mut() {
+                    Some(new_key) => new_key,
+                    None => return Probe::Absent,
+                };
+
+                if table.used.load(Ordering::Relaxed) >= table.capacity {
+                    return Probe::Full;
+                }
+
+                let new = unsafe { &*new_key.key };
+                new.tables.fetch_add(1, Ordering::Relaxed);
+
+                match slot.key.compare_exchange(
+                    ptr::null_mut(),
+                    new_key.key,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => {
+                        table.used.fetch_add(1, Ordering::Relaxed);
+                        new_key.claimed = true;
+                        return Probe::Found(slot);
+                    }
+                    Err(other) => {
+                        new.tables.fetch_sub(1, Ordering::Relaxed);
+                        current = other;
+                    }
+                }
+            }
+
+            if current == marker(MOVED) {
+                return Probe::Moved;
+            }
+
+            let current = unsafe { &*current };
+
+            if current.hash == hash && current.key == *key {
+                return Probe::Found(slot);
+            }
+
+            index = (index + 1) & table.mask();
+        }
+
+        if claim.is_some() {
+            Probe::Full
+        } else {
+            Probe::Absent
+        }
+    }
+
+    // put `new`(REMOVED to remove) on the key, and return the old value if it exists. The new
+    // key claims a slot if the key does not exist, and it is `None` to remove.
+    fn update(
+        &self,
+        key: &K,
+        hash: u64,
+        mut new_key: Option<&mut NewKey<K>>,
+        new: *mut Value<V>,
+        update: Update,
+        guard: &Guard,
+    ) -> Result<Option<NonNull<Value<V>>>, ()> {
+        'retry: loop {
+            let table = self.current(guard);
+
+            let slot = match self.probe(unsafe { &*table }, key, hash, new_key.as_deref_mut()) {
+                Probe::Found(slot) => slot,
+                Probe::Absent => return Err(()),
+                Probe::Moved | Probe::Full => {
+                    self.migrate(table, guard);
+                    continue;
+                }
+            };
+
+            let mut current = slot.value.load(Ordering::Acquire);
+
+            loop {
+                if is_frozen(current) {
+                    self.migrate(table, guard);
+                    continue 'retry;
+                }
+
+                let accepted = match update {
+                    Update::Insert => live(current).is_none(),
+                    Update::Replace => true,
+                    Update::Remove => live(current).is_some(),
+                };
+
+                if !accepted {
+                    return Err(());
+                }
+
+                match slot
+                    .value
+                    .compare_exchange(current, new, Ordering::AcqRel, Ordering::Acquire)
+                {
+                    Ok(_) => return Ok(live(current)),
+                    Err(other) => current = other,
+                }
+            }
+        }
+    }
+
+    // the table to update, after helping the migration in progress
+    fn current(&self, guard: &Guard) -> *mut Table<K, V> {
+        loop {
+            let table = self.table.load(Ordering::Acquire);
+
+            if unsafe { (*table).next.load(Ordering::Acquire) }.is_null() {
+                return table;
+            }
+
+            self.migrate(table, guard);
+        }
+    }
+
+    // migrate the table with the other threads, and return after the next table is current
+    fn migrate(&self, table_ptr: *mut Table<K, V>, guard: &Guard) {
+        let table = unsafe { &*table_ptr };
+        let size = table.slots.len();
+        let mut next_ptr = table.next.load(Ordering::Acquire);
+
+        if next_ptr.is_null() {
+            // the removed keys are cleaned up on the same size if they are the majority
+            let (used, removed) = table.slots.iter().fold((0, 0), |(used, removed), slot| {
+                let key = slot.key.load(Ordering::Relaxed);
+                let value = slot.value.load(Ordering::Relaxed);
+
+                if key.is_null() || key == marker(MOVED) {
+                    (used, removed)
+                } else if unfrozen(value) == marker(REMOVED) {
+                    (used + 1, removed + 1)
+                } else {
+                    (used + 1, removed)
+                }
+            });
+            let new_size = if removed > used - removed {
+                size
+            } else {
+                size << 1
+            };
+            let new = Box::into_raw(Box::new(Table::new(new_size, self.load_factor)));
+
+            next_ptr = match table.next.compare_exchange(
+                ptr::null_mut(),
+                new,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => new,
+                Err(other) => {
+                    drop(unsafe { Box::from_raw(new) });
+                    other
+                }
+            };
+        }
+
+        let next = unsafe { &*next_ptr };
+        let chunks = table.done.len();
+
+        loop {
+            let chunk = table.claimed.fetch_add(1, Ordering::Relaxed);
+
+            if chunk >= chunks {
+                break;
+            }
+
+            Self::migrate_chunk(table, chunk, next);
+        }
+
+        // the chunks claimed by the other threads may be stalled, so redo them
+        for chunk in 0..chunks {
+            if !table.done[chunk].load(Ordering::Acquire) {
+                Self::migrate_chunk(table, chunk, next);
+            }
+        }
+
+        if self
+            .table
+            .compare_exchange(table_ptr, next_ptr, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            unsafe { self.defer_drop(table_ptr, guard) };
+        }
+    }
+
+    fn migrate_chunk(table: &Table<K, V>, chunk: usize, next: &Table<K, V>) {
+        let start = chunk * CHUNK;
+        let end = (start + CHUNK).min(table.slots.len());
+
+        for index in start..end {
+            Self::migrate_slot(&table.slots[index], next);
+        }
+
+        table.done[chunk].store(true, Ordering::Release);
+    }
+
+    // Freeze the slot, and move the key to the next table unless it is removed. The key without
+    // the value is moved too, since the thread claiming it puts the value on the next table.
+    //
+    // It may run on the same slot by several threads, even after the next table is current, so
+    // every step is done once: the key is placed once, and the value is set only on null.
+    fn migrate_slot(slot: &Slot<K, V>, next: &Table<K, V>) {
+        let key = match slot.key.compare_exchange(
+            ptr::null_mut(),
+            marker(MOVED),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => return,
+            Err(key) if key == marker(MOVED) => return,
+            Err(key) => key,
+        };
+
+        let mut value = slot.value.load(Ordering::Acquire);
+
+        while let Err(other) =
+            slot.value
+                .compare_exchange(value, freeze(value), Ordering::AcqRel, Ordering::Acquire)
+        {
+            value = other;
+        }
+
+        // the value may be frozen already by the other thread
+        let value = unfrozen(value);
+
+        if value == marker(REMOVED) {
+            return;
+        }
+
+        // the key is placed on the first empty slot of its probe, so the threads moving the same
+        // key meet on the same slot
+        let mut index = unsafe { (*key).hash } as usize & next.mask();
+
+        let slot = loop {
+            let slot = &next.slots[index];
+
+            match slot.key.compare_exchange(
+                ptr::null_mut(),
+                key,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    next.used.fetch_add(1, Ordering::Relaxed);
+                    break slot;
+                }
+                Err(other) if other == key => break slot,
+                Err(_) => index = (index + 1) & next.mask(),
+            }
+        };
+
+        // the late thread does not overwrite the value updated on the next table
+        let _ = slot.value.compare_exchange(
+            ptr::null_mut(),
+            value,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+    }
+
+    // clone the removed value, and reclaim it later
+    unsafe fn retire(&self, value: NonNull<Value<V>>, guard: &Guard) -> V
+    where
+        V: Clone,
+    {
+        let cloned = value.as_ref().0.clone();
+        self.defer_drop(value.as_ptr(), guard);
+
+        cloned
+    }
+
+    // drop the box after the threads reading it are unpinned
+    #[cfg(not(loom))]
+    unsafe fn defer_drop<T>(&self, ptr: *mut T, guard: &Guard) {
+        // the global collector may run it on another thread after the map is dropped, which is
+        // sound since the boxes hold only `K: Send + 'static` and `V: Send + 'static`
+        guard.defer_unchecked(move || drop(Box::from_raw(ptr)));
+    }
+
+    #[cfg(loom)]
+    unsafe fn defer_drop<T>(&self, ptr: *mut T, _guard: &Guard) {
+        self.garbage
+            .lock()
+            .unwrap()
+            .push((ptr as *mut u8, drop_box::<T>));
+    }
+}
+
+impl<K, V, S> ConcurrentHashMap<K, V, S> for LockFreeHashMap<K, V, S>
+where
+    K: PartialEq + Hash + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Default + Send + Sync,
+{
+    type Guard<'a>
+        = Ref<'a, V>
+    where
+        Self: 'a;
+
+    fn new() -> Self {
+        Self::with_hasher(S::default())
+    }
+
+    fn with_hasher(hasher: S) -> Self {
+        Self::new_with_properties(hasher, INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    /// Insert the value if the key does not exist.
+    fn insert(&self, key: K, value: V) -> Result<(), V> {
+        let guard = pin();
+        let hash = self.hasher.hash_one(&key);
+        let mut new_key = NewKey::new(key, hash);
+        // the key is read through the pointer, since it may be taken by the table
+        let key = unsafe { &(*new_key.key).key };
+        let value = Box::into_raw(Box::new(Value(value)));
+
+        match self.update(key, hash, Some(&mut new_key), value, Update::Insert, &guard) {
+            Ok(_) => {
+                self.len.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            // the value is not published
+            Err(()) => Err(unsafe { Box::from_raw(value) }.0),
+        }
+    }
+
+    fn lookup(&self, key: &K) -> Option<Self::Guard<'_>> {
+        let guard = pin();
+        let hash = self.hasher.hash_one(key);
+
+        loop {
+            let table = self.table.load(Ordering::Acquire);
+
+            let value = match self.probe(unsafe { &*table }, key, hash, None) {
+                Probe::Found(slot) => slot.value.load(Ordering::Acquire),
+                Probe::Moved => freeze(ptr::null_mut()),
+                Probe::Absent | Probe::Full => ptr::null_mut(),
+            };
+
+            // the frozen slot may be updated on the next table, so it is valid only if the table
+            // is still the current one. Otherwise, the migration has not reached the slot yet.
+            if is_frozen(value) && self.table.load(Ordering::Acquire) != table {
+                continue;
+            }
+
+            return live(value).map(|value| Ref {
+                _guard: guard,
+                value: unsafe { NonNull::from(&value.as_ref().0) },
+                _marker: PhantomData,
+            });
+        }
+    }
+
+    /// Remove the key, and return the clone of the value, since the value may be still read by
+    /// the other threads until it is reclaimed.
+    fn remove(&self, key: &K) -> Result<V, ()> {
+        let guard = pin();
+        let hash = self.hasher.hash_one(key);
+        let value = self.update(key, hash, None, marker(REMOVED), Update::Remove, &guard)?;
+
+        self.len.fetch_sub(1, Ordering::Relaxed);
+        Ok(unsafe { self.retire(value.unwrap(), &guard) })
+    }
+
+    /// Insert the value even if the key exists, and return the clone of the replaced value.
+    fn insert_or_replace(&self, key: K, value: V) -> Option<V> {
+        let guard = pin();
+        let hash = self.hasher.hash_one(&key);
+        let mut new_key = NewKey::new(key, hash);
+        let key = unsafe { &(*new_key.key).key };
+        let value = Box::into_raw(Box::new(Value(value)));
+        let old = self
+            .update(
+                key,
+                hash,
+                Some(&mut new_key),
+                value,
+                Update::Replace,
+                &guard,
+            )
+            .expect("The replace does not fail.");
+
+        if let Some(old) = old {
+            Some(unsafe { self.retire(old, &guard) })
+        } else {
+            self.len.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+
+    /// The number of the keys, which may be changing by the other threads.
+    fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed).max(0) as usize
+    }
+
+    /// Remove all keys on the table one by one, so it is not atomic to the other updates.
+    fn clear(&self) {
+        let guard = pin();
+        let table = unsafe { &*self.current(&guard) };
+
+        for slot in table.slots.iter() {
+            let key = slot.key.load(Ordering::Acquire);
+
+            if key.is_null() || key == marker(MOVED) {
+                continue;
+            }
+
+            if let Ok(Some(value)) = self.update(
+                unsafe { &(*key).key },
+                unsafe { (*key).hash },
+                None,
+                marker(REMOVED),
+                Update::Remove,
+                &guard,
+            ) {
+                self.len.fetch_sub(1, Ordering::Relaxed);
+                unsafe { self.defer_drop(value.as_ptr(), &guard) };
+            }
+        }
+    }
+}
+
+impl<K, V, S> Drop for LockFreeHashMap<K, V, S> {
+    fn drop(&mut self) {
+        unsafe {
+            drop(Box::from_raw(self.table.load(Ordering::Relaxed)));
+
+            #[cfg(loom)]
+            for (ptr, drop) in self.garbage.lock().unwrap().drain(..) {
+                drop(ptr);
+            }
+        }
+    }
+}
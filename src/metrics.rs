@@ -0,0 +1,43 @@
+use std::cell::Cell;
+
+/// The counters of the operations on the open addressing tables, which are counted per thread
+/// with the `metrics` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// The buckets visited by the lookups.
+    pub probes: u64,
+    /// The hashes compared with the ones on the buckets.
+    pub comparisons: u64,
+    /// The hashes computed from the keys, including the ones for the probe sequences.
+    pub hashes: u64,
+    /// The keys compared after their hashes are matched.
+    pub key_eq_checks: u64,
+    /// The buckets evicted by the LCFS insertions.
+    pub displacements: u64,
+    pub resizes: u64,
+    /// The bytes of the buckets moved to the new table by the resizes.
+    pub bytes_moved: u64,
+}
+
+thread_local! {
+    static METRICS: Cell<Metrics> = Cell::new(Metrics::default());
+}
+
+impl Metrics {
+    /// The counters of the current thread since the last reset.
+    pub fn snapshot() -> Self {
+        METRICS.with(|metrics| metrics.get())
+    }
+
+    pub fn reset() {
+        METRICS.with(|metrics| metrics.set(Metrics::default()));
+    }
+}
+
+pub(crate) fn add(f: impl FnOnce(&mut Metrics)) {
+    METRICS.with(|metrics| {
+        let mut current = metrics.get();
+        f(&mut current);
+        metrics.set(current);
+    });
+}
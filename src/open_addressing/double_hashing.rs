@@ -1,94 +1,78 @@
 use std::hash::Hash;
-use std::hash::Hasher;
-use std::ptr;
-use std::{
-    collections::hash_map::{DefaultHasher, RandomState},
-    hash::BuildHasher,
-};
+use std::{collections::hash_map::RandomState, hash::BuildHasher};
 
-use crate::{Entry, InsertResult, RawHashTable};
+use super::{Fcfs, Lcfs, ProbeSequence};
 
-use super::{Bucket, EntryBucket, FCFS};
-
-pub struct FcfsDoubleHashing<T = DefaultHasher> {
-    hasher: Box<dyn BuildHasher<Hasher = T>>,
-    tombstone: bool,
+/// The probe sequence stepping by the second hash of the key
+pub struct DoubleHashing<H = RandomState> {
+    hasher: H,
 }
 
-impl Default for FcfsDoubleHashing {
-    fn default() -> Self {
-        Self {
-            hasher: Box::new(RandomState::new()),
-            tombstone: true,
-        }
+impl<H: BuildHasher> DoubleHashing<H> {
+    /// The step of the probe is hashed by `hasher`, which should be independent of the hasher of
+    /// the table.
+    pub fn with_hasher(hasher: H) -> Self {
+        Self { hasher }
     }
 }
 
-impl FcfsDoubleHashing {
-    fn hash_one<K: Hash>(&self, x: K) -> u64 {
-        let mut hasher = self.hasher.build_hasher();
-        x.hash(&mut hasher);
-        hasher.finish()
+impl<H: BuildHasher + Default> Default for DoubleHashing<H> {
+    fn default() -> Self {
+        Self::with_hasher(H::default())
     }
 }
 
-impl<K: PartialEq + Hash, V> Entry<K, Bucket<K, V>> for FcfsDoubleHashing {
-    fn insert(&mut self, table: &RawHashTable, bucket: Bucket<K, V>) -> InsertResult<Bucket<K, V>> {
-        let mut step: usize = 0;
-        let second_hash = self.hash_one(&bucket.key) as usize;
+pub struct DoubleHashingProbe {
+    offset: usize,
+    step: usize,
+}
 
-        let offset = || {
-            step = step.wrapping_add(second_hash);
-            step
-        };
+impl Iterator for DoubleHashingProbe {
+    type Item = usize;
 
-        if let Ok(entry_bucket) = FCFS::lookup(table, &bucket.key, bucket.hash, offset) {
-            match entry_bucket {
-                EntryBucket::Some(_) => InsertResult::AlreadyExist(bucket),
-                EntryBucket::None | EntryBucket::Tombstone => {
-                    unsafe { ptr::write(entry_bucket, EntryBucket::Some(bucket)) };
-                    InsertResult::Success
-                }
-            }
-        } else {
-            InsertResult::Full(bucket)
-        }
+    fn next(&mut self) -> Option<Self::Item> {
+        self.offset = self.offset.wrapping_add(self.step);
+        Some(self.offset)
     }
+}
 
-    fn lookup<'a>(&self, table: &'a RawHashTable, key: &K, hash: u64) -> Option<&'a Bucket<K, V>> {
-        let mut step: usize = 0;
-        let second_hash = self.hash_one(key) as usize;
-
-        let offset = || {
-            step = step.wrapping_add(second_hash);
-            step
-        };
+impl<H: BuildHasher> ProbeSequence for DoubleHashing<H> {
+    type Probe = DoubleHashingProbe;
 
-        if let Ok(entry_bucket) = FCFS::lookup(table, key, hash, offset) {
-            match entry_bucket {
-                EntryBucket::None => None,
-                EntryBucket::Some(bucket) => Some(bucket),
-                EntryBucket::Tombstone => None,
-            }
-        } else {
-            None
+    fn probe<K: ?Sized + Hash>(&self, key: &K, _: usize) -> Self::Probe {
+        metric!(hashes);
+        DoubleHashingProbe {
+            offset: 0,
+            // the odd step is co-prime with the power-of-two size, so it visits every bucket
+            step: self.hasher.hash_one(key) as usize | 1,
         }
     }
+}
 
-    fn remove(&mut self, table: &RawHashTable, key: &K, hash: u64) -> Result<Bucket<K, V>, ()> {
-        let mut step: usize = 0;
-        let second_hash = self.hash_one(key) as usize;
+/// The removal always leaves a tombstone, since the backward shift needs the linear probing.
+pub type FcfsDoubleHashing<H = RandomState> = Fcfs<DoubleHashing<H>>;
+pub type LcfsDoubleHashing<H = RandomState> = Lcfs<DoubleHashing<H>>;
 
-        let offset = || {
-            step = step.wrapping_add(second_hash);
-            step
-        };
+impl Fcfs<DoubleHashing> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<H: BuildHasher> Fcfs<DoubleHashing<H>> {
+    pub fn with_hasher(hasher: H) -> Self {
+        Self::with_probe(DoubleHashing::with_hasher(hasher), true)
+    }
+}
 
-        let entry_bucket = FCFS::remove(table, key, hash, offset, self.tombstone)?;
+impl Lcfs<DoubleHashing> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
 
-        match entry_bucket {
-            EntryBucket::Some(bucket) => Ok(bucket),
-            _ => Err(()),
-        }
+impl<H: BuildHasher> Lcfs<DoubleHashing<H>> {
+    pub fn with_hasher(hasher: H) -> Self {
+        Self::with_probe(DoubleHashing::with_hasher(hasher), true)
     }
 }
@@ -0,0 +1,122 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{EntryStrategy, OccupiedEntry, VacantEntry};
+
+use super::{Bucket, EntryBucket, OpenAddressingHashTable};
+
+pub struct Occupied<'a, K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    pub(crate) table: &'a mut OpenAddressingHashTable<K, V, E, S>,
+    pub(crate) index: usize,
+}
+
+impl<'a, K, V, E, S> Occupied<'a, K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    // the bucket is not moved while the entry holds the table
+    fn bucket(&self) -> &Bucket<K, V> {
+        match EntryBucket::<K, V>::at(&self.table.hashtable.inner, self.index) {
+            EntryBucket::Some(bucket) => bucket,
+            _ => unreachable!(),
+        }
+    }
+
+    fn bucket_mut(&mut self) -> &mut Bucket<K, V> {
+        match EntryBucket::<K, V>::at_mut(&mut self.table.hashtable.inner, self.index) {
+            EntryBucket::Some(bucket) => bucket,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a, K, V, E, S> OccupiedEntry<'a> for Occupied<'a, K, V, E, S>
+where
+    K: PartialEq + Hash + 'a,
+    V: 'a,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.bucket().key
+    }
+
+    fn get(&self) -> &V {
+        &self.bucket().value
+    }
+
+    fn get_mut(&mut self) -> &mut V {
+        &mut self.bucket_mut().value
+    }
+
+    fn into_mut(self) -> &'a mut V {
+        match EntryBucket::<K, V>::at_mut(&mut self.table.hashtable.inner, self.index) {
+            EntryBucket::Some(bucket) => &mut bucket.value,
+            _ => unreachable!(),
+        }
+    }
+
+    fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    fn remove(self) -> V {
+        self.table.remove_at(self.index).value
+    }
+}
+
+pub struct Vacant<'a, K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    pub(crate) table: &'a mut OpenAddressingHashTable<K, V, E, S>,
+    pub(crate) key: K,
+    pub(crate) hash: u64,
+    pub(crate) index: usize,
+}
+
+impl<'a, K, V, E, S> VacantEntry<'a> for Vacant<'a, K, V, E, S>
+where
+    K: PartialEq + Hash + 'a,
+    V: 'a,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn into_key(self) -> K {
+        self.key
+    }
+
+    fn insert(self, value: V) -> &'a mut V {
+        let bucket = Bucket {
+            key: self.key,
+            hash: self.hash,
+            value,
+        };
+
+        let table = self.table;
+        let index = table.insert_at(self.index, bucket);
+
+        match EntryBucket::<K, V>::at_mut(&mut table.hashtable.inner, index) {
+            EntryBucket::Some(bucket) => &mut bucket.value,
+            _ => unreachable!(),
+        }
+    }
+}
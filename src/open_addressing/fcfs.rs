@@ -1,83 +1,423 @@
+use std::borrow::Borrow;
+use std::hash::Hash;
 use std::mem;
 
-use crate::RawHashTable;
+use crate::{EntryResult, EntryStrategy, InsertResult, RawHashTable};
 
-use super::EntryBucket;
+use super::{Bucket, EntryBucket, ProbeSequence};
 
 pub struct FCFS;
 
 impl FCFS {
-    pub fn lookup<'a, K, V, F>(
-        table: &'a RawHashTable,
-        key: &K,
-        hash: u64,
-        mut offset: F,
-    ) -> Result<&'a mut EntryBucket<K, V>, ()>
+    // find the key by the shared references, or the first tombstone or None to insert it
+    pub fn entry<K, V, Q, F>(table: &RawHashTable, key: &Q, hash: u64, mut offset: F) -> EntryResult
     where
-        K: PartialEq,
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
         F: FnMut() -> usize,
     {
         let hash_index = hash as usize & table.mask;
-
-        let first_bucket = table.buckets.as_ptr() as *mut u8 as *mut EntryBucket<K, V>;
-        let mut bucket = unsafe { &mut *first_bucket.add(hash_index) };
-
-        let mut tombstone_ptr = None;
+        let mut index = hash_index;
+        let mut tombstone = None;
 
         loop {
-            match bucket {
-                EntryBucket::None => {
-                    return if tombstone_ptr.is_some() {
-                        Ok(tombstone_ptr.unwrap())
-                    } else {
-                        Ok(bucket)
-                    };
-                }
+            metric!(probes);
+
+            match EntryBucket::<K, V>::at(table, index) {
+                EntryBucket::None => return EntryResult::None(tombstone.unwrap_or(index)),
                 EntryBucket::Tombstone => {
-                    if tombstone_ptr.is_none() {
-                        tombstone_ptr = Some(bucket);
+                    if tombstone.is_none() {
+                        tombstone = Some(index);
                     }
                 }
                 EntryBucket::Some(entry_bucket) => {
-                    if entry_bucket.hash == hash && entry_bucket.key == *key {
-                        return Ok(bucket);
+                    metric!(comparisons);
+
+                    if entry_bucket.hash == hash {
+                        metric!(key_eq_checks);
+
+                        if entry_bucket.key.borrow() == key {
+                            return EntryResult::Some(index);
+                        }
                     }
                 }
             }
 
-            let next_index = hash_index.wrapping_add(offset()) & table.mask;
+            index = hash_index.wrapping_add(offset()) & table.mask;
 
-            if next_index == hash_index {
-                return Err(());
+            if index == hash_index {
+                return EntryResult::Full;
             }
+        }
+    }
 
-            unsafe { bucket = &mut *first_bucket.add(next_index) }
+    pub fn lookup<'a, K, V, Q, F>(
+        table: &'a RawHashTable,
+        key: &Q,
+        hash: u64,
+        offset: F,
+    ) -> Option<&'a Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+        F: FnMut() -> usize,
+    {
+        match Self::entry::<K, V, Q, F>(table, key, hash, offset) {
+            EntryResult::Some(index) => match EntryBucket::at(table, index) {
+                EntryBucket::Some(bucket) => Some(bucket),
+                _ => unreachable!(),
+            },
+            EntryResult::None(_) | EntryResult::Full => None,
         }
     }
 
-    pub fn remove<K, V, F>(
-        table: &RawHashTable,
-        key: &K,
+    pub fn lookup_mut<'a, K, V, Q, F>(
+        table: &'a mut RawHashTable,
+        key: &Q,
+        hash: u64,
+        offset: F,
+    ) -> Option<&'a mut Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+        F: FnMut() -> usize,
+    {
+        match Self::entry::<K, V, Q, F>(table, key, hash, offset) {
+            EntryResult::Some(index) => match EntryBucket::at_mut(table, index) {
+                EntryBucket::Some(bucket) => Some(bucket),
+                _ => unreachable!(),
+            },
+            EntryResult::None(_) | EntryResult::Full => None,
+        }
+    }
+
+    // return the index, and whether the bucket takes a tombstone
+    pub fn insert_at<K, V>(
+        table: &mut RawHashTable,
+        index: usize,
+        bucket: Bucket<K, V>,
+    ) -> (usize, bool) {
+        let entry_bucket = EntryBucket::at_mut(table, index);
+        let tombstone = matches!(entry_bucket, EntryBucket::Tombstone);
+
+        *entry_bucket = EntryBucket::Some(bucket);
+        (index, tombstone)
+    }
+
+    #[allow(clippy::result_unit_err)]
+    pub fn remove<K, V, Q, F>(
+        table: &mut RawHashTable,
+        key: &Q,
         hash: u64,
         offset: F,
         tombstone: bool,
     ) -> Result<EntryBucket<K, V>, ()>
     where
-        K: PartialEq,
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
         F: FnMut() -> usize,
     {
+        match Self::entry::<K, V, Q, F>(table, key, hash, offset) {
+            EntryResult::Some(index) => Ok(Self::remove_at(table, index, tombstone)),
+            EntryResult::None(_) | EntryResult::Full => Err(()),
+        }
+    }
+
+    // without `tombstone`, the probe sequence should visit the next bucket on each step
+    pub fn remove_at<K, V>(
+        table: &mut RawHashTable,
+        index: usize,
+        tombstone: bool,
+    ) -> EntryBucket<K, V> {
+        let entry_bucket = EntryBucket::at_mut(table, index);
+
         if tombstone {
-            if let Ok(entry_bucket) = Self::lookup(table, key, hash, offset) {
-                match entry_bucket {
-                    EntryBucket::None => Err(()),
-                    EntryBucket::Some(_) => Ok(mem::replace(entry_bucket, EntryBucket::Tombstone)),
-                    EntryBucket::Tombstone => Err(()),
+            mem::replace(entry_bucket, EntryBucket::Tombstone)
+        } else {
+            let removed = mem::replace(entry_bucket, EntryBucket::None);
+
+            Self::backshift::<K, V>(table, index);
+            removed
+        }
+    }
+
+    // the number of the buckets on the probe sequence from the home of `hash` to `index`
+    pub fn probe_length<F>(table: &RawHashTable, hash: u64, index: usize, mut offset: F) -> usize
+    where
+        F: FnMut() -> usize,
+    {
+        let hash_index = hash as usize & table.mask;
+        let mut probe = hash_index;
+        let mut length = 1;
+
+        while probe != index {
+            probe = hash_index.wrapping_add(offset()) & table.mask;
+            length += 1;
+        }
+
+        length
+    }
+
+    // the number of the buckets on the probe sequence from `home` to None, or to the home again
+    pub fn miss_length<K, V, F>(table: &RawHashTable, home: usize, mut offset: F) -> usize
+    where
+        F: FnMut() -> usize,
+    {
+        let mut probe = home;
+        let mut length = 1;
+
+        while !matches!(EntryBucket::<K, V>::at(table, probe), EntryBucket::None) {
+            probe = home.wrapping_add(offset()) & table.mask;
+
+            if probe == home {
+                break;
+            }
+
+            length += 1;
+        }
+
+        length
+    }
+
+    // Purge all tombstones in place. The tombstones become None, and each bucket is placed again
+    // on the first None or not yet placed bucket along its probe sequence, swapping with the
+    // latter. The placed buckets are never moved again, so the lookups stopping at None reach
+    // every bucket.
+    pub fn rehash_in_place<K, V, G, F>(table: &mut RawHashTable, offset_of: G)
+    where
+        G: Fn(&K) -> F,
+        F: FnMut() -> usize,
+    {
+        let mut unplaced = vec![false; table.mask + 1];
+
+        for (index, unplaced) in unplaced.iter_mut().enumerate() {
+            let entry_bucket = EntryBucket::<K, V>::at_mut(table, index);
+
+            match entry_bucket {
+                EntryBucket::Tombstone => *entry_bucket = EntryBucket::None,
+                EntryBucket::Some(_) => *unplaced = true,
+                EntryBucket::None => {}
+            }
+        }
+
+        for index in 0..=table.mask {
+            while unplaced[index] {
+                let target = match EntryBucket::<K, V>::at(table, index) {
+                    EntryBucket::Some(bucket) => {
+                        Self::first_unplaced(table, bucket, &unplaced, offset_of(&bucket.key))
+                    }
+                    _ => unreachable!(),
+                };
+
+                if target != index {
+                    let bucket = mem::replace(EntryBucket::at_mut(table, index), EntryBucket::None);
+                    let swapped = mem::replace(EntryBucket::<K, V>::at_mut(table, target), bucket);
+                    *EntryBucket::at_mut(table, index) = swapped;
                 }
-            } else {
-                Err(())
+
+                // the bucket swapped from `target` is placed again from `index`
+                unplaced[index] = target != index && unplaced[target];
+                unplaced[target] = false;
             }
+        }
+    }
+
+    // the first None or not yet placed bucket on the probe sequence of `bucket`, which may be the
+    // bucket itself
+    fn first_unplaced<K, V, F>(
+        table: &RawHashTable,
+        bucket: &Bucket<K, V>,
+        unplaced: &[bool],
+        mut offset: F,
+    ) -> usize
+    where
+        F: FnMut() -> usize,
+    {
+        let hash_index = bucket.hash as usize & table.mask;
+        let mut probe = hash_index;
+
+        while !unplaced[probe]
+            && !matches!(EntryBucket::<K, V>::at(table, probe), EntryBucket::None)
+        {
+            probe = hash_index.wrapping_add(offset()) & table.mask;
+        }
+
+        probe
+    }
+
+    // Fill the hole at `hole` by moving back the buckets whose probe sequence passes through it,
+    // so that lookups stopping at `EntryBucket::None` still reach every bucket. The probe sequence
+    // should visit the next bucket on each step(linear probing), so the candidates are only in the
+    // cluster after the hole.
+    pub fn backshift<K, V>(table: &mut RawHashTable, mut hole: usize) {
+        let mut index = hole;
+
+        loop {
+            index = (index + 1) & table.mask;
+
+            let home = match EntryBucket::<K, V>::at(table, index) {
+                EntryBucket::None => return,
+                EntryBucket::Tombstone => continue,
+                EntryBucket::Some(bucket) => bucket.hash as usize & table.mask,
+            };
+
+            // the bucket passes the hole unless its home is between the hole and it
+            if index.wrapping_sub(home) & table.mask >= index.wrapping_sub(hole) & table.mask {
+                let bucket =
+                    mem::replace(EntryBucket::<K, V>::at_mut(table, index), EntryBucket::None);
+                *EntryBucket::at_mut(table, hole) = bucket;
+                hole = index;
+            }
+        }
+    }
+}
+
+/// The FCFS entry strategy on the probe sequence `P`
+pub struct Fcfs<P> {
+    probe: P,
+    tombstone: bool,
+}
+
+impl<P: ProbeSequence> Fcfs<P> {
+    /// If `tombstone` is false, the removal fills the hole by backward shift instead of leaving
+    /// a tombstone.
+    ///
+    /// # Panics
+    ///
+    /// The backward shift needs the probe sequence visiting the next bucket on each
+    /// step(linear probing), so it panics if `tombstone` is false on the other probe sequences.
+    pub fn with_probe(probe: P, tombstone: bool) -> Self {
+        assert!(
+            tombstone || probe.contiguous(),
+            "backward shift needs a contiguous probe sequence"
+        );
+
+        Self { probe, tombstone }
+    }
+
+    fn offset<K: ?Sized + Hash>(&self, key: &K, mask: usize) -> impl FnMut() -> usize {
+        let mut probe = self.probe.probe(key, mask);
+
+        // the end of the sequence returns to the home, which stops the walk
+        move || probe.next().unwrap_or(0)
+    }
+}
+
+impl<P: ProbeSequence + Default> Default for Fcfs<P> {
+    fn default() -> Self {
+        Self::with_probe(P::default(), true)
+    }
+}
+
+impl<K: PartialEq + Hash, V, P: ProbeSequence + Default> EntryStrategy<K, Bucket<K, V>>
+    for Fcfs<P>
+{
+    fn insert(
+        &mut self,
+        table: &mut RawHashTable,
+        bucket: Bucket<K, V>,
+    ) -> InsertResult<Bucket<K, V>> {
+        let offset = self.offset(&bucket.key, table.mask);
+
+        match FCFS::entry::<K, V, K, _>(table, &bucket.key, bucket.hash, offset) {
+            EntryResult::Some(_) => InsertResult::AlreadyExist(bucket),
+            EntryResult::None(index) => {
+                FCFS::insert_at(table, index, bucket);
+                InsertResult::Success
+            }
+            EntryResult::Full => InsertResult::Full(bucket),
+        }
+    }
+
+    fn lookup<'a, Q>(&self, table: &'a RawHashTable, key: &Q, hash: u64) -> Option<&'a Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        FCFS::lookup(table, key, hash, self.offset(key, table.mask))
+    }
+
+    fn lookup_mut<'a, Q>(
+        &self,
+        table: &'a mut RawHashTable,
+        key: &Q,
+        hash: u64,
+    ) -> Option<&'a mut Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        FCFS::lookup_mut(table, key, hash, self.offset(key, table.mask))
+    }
+
+    fn remove<Q>(
+        &mut self,
+        table: &mut RawHashTable,
+        key: &Q,
+        hash: u64,
+    ) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let offset = self.offset(key, table.mask);
+        let entry_bucket = FCFS::remove(table, key, hash, offset, self.tombstone)?;
+
+        match entry_bucket {
+            EntryBucket::Some(bucket) => Ok(bucket),
+            _ => Err(()),
+        }
+    }
+
+    fn entry<Q>(&self, table: &RawHashTable, key: &Q, hash: u64) -> EntryResult
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        FCFS::entry::<K, V, Q, _>(table, key, hash, self.offset(key, table.mask))
+    }
+
+    fn insert_at(
+        &mut self,
+        table: &mut RawHashTable,
+        index: usize,
+        bucket: Bucket<K, V>,
+    ) -> Result<(usize, bool), Bucket<K, V>> {
+        Ok(FCFS::insert_at(table, index, bucket))
+    }
+
+    fn remove_at(&mut self, table: &mut RawHashTable, index: usize) -> Bucket<K, V> {
+        let entry_bucket = FCFS::remove_at(table, index, self.tombstone);
+
+        if let EntryBucket::Some(bucket) = entry_bucket {
+            bucket
         } else {
-            todo!("Backshift is not implemented now.")
+            unreachable!()
         }
     }
+
+    fn rehash_in_place(&mut self, table: &mut RawHashTable) {
+        let mask = table.mask;
+
+        FCFS::rehash_in_place::<K, V, _, _>(table, |key| self.offset(key, mask));
+    }
+
+    fn probe_length(&self, table: &RawHashTable, index: usize) -> usize {
+        if let EntryBucket::<K, V>::Some(bucket) = EntryBucket::at(table, index) {
+            FCFS::probe_length(
+                table,
+                bucket.hash,
+                index,
+                self.offset(&bucket.key, table.mask),
+            )
+        } else {
+            unreachable!()
+        }
+    }
+
+    fn miss_length(&self, table: &RawHashTable, home: usize) -> usize {
+        // no absent key is at hand, so the home stands in for it, which draws another sequence
+        // than a real key on double hashing and random probing
+        FCFS::miss_length::<K, V, _>(table, home, self.offset(&home, table.mask))
+    }
 }
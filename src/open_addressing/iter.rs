@@ -0,0 +1,149 @@
+use std::hash::{BuildHasher, Hash};
+use std::{mem, slice};
+
+use crate::{EntryStrategy, RawHashTable};
+
+use super::{Bucket, EntryBucket, OpenAddressingHashTable};
+
+/// Iterator over the live buckets, skipping `EntryBucket::None` and `EntryBucket::Tombstone`.
+pub struct Iter<'a, K, V> {
+    buckets: slice::Iter<'a, EntryBucket<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    pub(crate) fn new(table: &'a RawHashTable) -> Self {
+        let buckets = unsafe {
+            slice::from_raw_parts(
+                table.buckets.as_ptr() as *const EntryBucket<K, V>,
+                table.mask + 1,
+            )
+        };
+
+        Self {
+            buckets: buckets.iter(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buckets.find_map(|entry_bucket| match entry_bucket {
+            EntryBucket::Some(bucket) => Some((&bucket.key, &bucket.value)),
+            _ => None,
+        })
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    buckets: slice::IterMut<'a, EntryBucket<K, V>>,
+}
+
+impl<'a, K, V> IterMut<'a, K, V> {
+    pub(crate) fn new(table: &'a mut RawHashTable) -> Self {
+        let buckets = unsafe {
+            slice::from_raw_parts_mut(
+                table.buckets.as_ptr() as *mut EntryBucket<K, V>,
+                table.mask + 1,
+            )
+        };
+
+        Self {
+            buckets: buckets.iter_mut(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buckets.find_map(|entry_bucket| match entry_bucket {
+            EntryBucket::Some(bucket) => Some((&bucket.key, &mut bucket.value)),
+            _ => None,
+        })
+    }
+}
+
+/// Owning iterator, taking out the buckets from the table. The table still owns the bucket array
+/// and frees it on drop.
+pub struct IntoIter<K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    table: OpenAddressingHashTable<K, V, E, S>,
+    index: usize,
+}
+
+impl<K, V, E, S> Iterator for IntoIter<K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let inner = &self.table.hashtable.inner;
+        let first_bucket = inner.buckets.as_ptr() as *mut EntryBucket<K, V>;
+
+        while self.index <= inner.mask {
+            let entry_bucket = unsafe { &mut *first_bucket.add(self.index) };
+            self.index += 1;
+
+            if let EntryBucket::Some(bucket) = mem::replace(entry_bucket, EntryBucket::None) {
+                return Some((bucket.key, bucket.value));
+            }
+        }
+
+        None
+    }
+}
+
+impl<K, V, E, S> IntoIterator for OpenAddressingHashTable<K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, E, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            table: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, K, V, E, S> IntoIterator for &'a OpenAddressingHashTable<K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(&self.hashtable.inner)
+    }
+}
+
+impl<'a, K, V, E, S> IntoIterator for &'a mut OpenAddressingHashTable<K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IterMut::new(&mut self.hashtable.inner)
+    }
+}
@@ -1,173 +1,313 @@
-use std::{
-    mem,
-    ptr::{self, NonNull},
-};
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::mem;
 
-use crate::{InsertResult, RawHashTable};
+use crate::{EntryResult, EntryStrategy, InsertResult, RawHashTable};
 
-use super::{Bucket, EntryBucket};
+use super::{Bucket, EntryBucket, ProbeSequence, FCFS};
 
 pub struct LCFS;
 
 impl LCFS {
-    // stack up for insert and remove
-    fn lookup_stack<K, V, F>(
-        table: &RawHashTable,
-        key: &K,
-        hash: u64,
-        mut offset: F,
-        tombstone: bool,
-    ) -> Result<Vec<NonNull<EntryBucket<K, V>>>, ()>
+    pub fn insert<K, V, G, F>(
+        table: &mut RawHashTable,
+        offset_of: G,
+        bucket: Bucket<K, V>,
+    ) -> InsertResult<Bucket<K, V>>
     where
         K: PartialEq,
+        G: Fn(&K) -> F,
         F: FnMut() -> usize,
     {
-        let hash_index = hash as usize & table.mask;
-
-        let first_bucket = table.buckets.as_ptr() as *const u8 as *const EntryBucket<K, V>;
-        let mut bucket = unsafe { first_bucket.add(hash_index) };
-        let mut buckets = Vec::new();
-
-        let mut tombstone_buckets = None;
-
-        loop {
-            buckets.push(NonNull::new(bucket as *mut _).unwrap());
-
-            match unsafe { &*bucket } {
-                EntryBucket::None => {
-                    return if let Some(result) = tombstone_buckets {
-                        Ok(result)
-                    } else {
-                        Ok(buckets)
-                    };
-                }
-                EntryBucket::Tombstone => {
-                    debug_assert!(tombstone);
-
-                    if tombstone_buckets.is_none() {
-                        tombstone_buckets = Some(buckets.clone());
-                    }
-                }
-                EntryBucket::Some(entry_bucket) => {
-                    // todo implement for backshift on removal
-                    if entry_bucket.hash == hash && entry_bucket.key == *key {
-                        return Ok(buckets);
-                    }
-                }
-            }
-
-            let next_index = hash_index.wrapping_add(offset()) & table.mask;
-
-            if next_index == hash_index {
-                // insert: the table is full!
-                // remove: there does not exist key!
-                return Err(());
-            }
-
-            unsafe { bucket = first_bucket.add(next_index) }
+        match Self::entry::<K, V, K, F>(table, &bucket.key, bucket.hash, offset_of(&bucket.key)) {
+            EntryResult::Some(_) => InsertResult::AlreadyExist(bucket),
+            EntryResult::None(_) => match Self::insert_at(table, offset_of, bucket) {
+                Ok(_) => InsertResult::Success,
+                Err(bucket) => InsertResult::Full(bucket),
+            },
+            EntryResult::Full => InsertResult::Full(bucket),
         }
     }
 
-    pub fn insert<K, V, F>(
-        table: &RawHashTable,
-        offset: F,
+    pub fn entry<K, V, Q, F>(table: &RawHashTable, key: &Q, hash: u64, offset: F) -> EntryResult
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+        F: FnMut() -> usize,
+    {
+        // the free bucket is found as FCFS, but it is filled by the last evicted bucket
+        FCFS::entry::<K, V, Q, F>(table, key, hash, offset)
+    }
+
+    // Place the bucket on its home, and move the evicted bucket along its own probe sequence to
+    // the first None or tombstone after there. The buckets passed on the way stay, so the lookup
+    // of the evicted bucket still reaches it.
+    //
+    // It returns the index where the bucket is placed, and whether a tombstone is taken. If the
+    // probe sequence of the evicted bucket does not reach a free bucket(ex. the classic quadratic
+    // probing), the table is left as it was and the bucket is given back to resize the table.
+    pub fn insert_at<K, V, G, F>(
+        table: &mut RawHashTable,
+        offset_of: G,
         bucket: Bucket<K, V>,
-        tombstone: bool,
-    ) -> InsertResult<Bucket<K, V>>
+    ) -> Result<(usize, bool), Bucket<K, V>>
     where
-        K: PartialEq,
+        G: Fn(&K) -> F,
         F: FnMut() -> usize,
     {
-        let mut buckets = if let Ok(buckets) =
-            LCFS::lookup_stack(table, &bucket.key, bucket.hash, offset, tombstone)
-        {
-            buckets
-        } else {
-            return InsertResult::Full(bucket);
+        let home = bucket.hash as usize & table.mask;
+
+        let evicted = match EntryBucket::at_mut(table, home) {
+            EntryBucket::Some(evicted) => mem::replace(evicted, bucket),
+            entry_bucket => {
+                let tombstone = matches!(entry_bucket, EntryBucket::Tombstone);
+                *entry_bucket = EntryBucket::Some(bucket);
+                return Ok((home, tombstone));
+            }
         };
 
-        let mut last_bucket = buckets.pop().unwrap();
+        // walk the probe sequence of the evicted bucket up to `home`, and carry it on from there,
+        // for the buckets of the table at most
+        let hash_index = evicted.hash as usize & table.mask;
+        let mut offset = offset_of(&evicted.key);
+        let mut position = hash_index;
+        let mut steps = 0;
 
-        if let EntryBucket::Some(_) = unsafe { last_bucket.as_ref() } {
-            return InsertResult::AlreadyExist(bucket);
+        while position != home && steps <= table.mask {
+            position = hash_index.wrapping_add(offset()) & table.mask;
+            steps += 1;
         }
 
-        while let Some(b) = buckets.pop() {
-            unsafe {
-                ptr::write(last_bucket.as_mut(), ptr::read(b.as_ref()));
+        while steps <= table.mask {
+            position = hash_index.wrapping_add(offset()) & table.mask;
+            steps += 1;
+
+            let entry_bucket = EntryBucket::at_mut(table, position);
+
+            if let EntryBucket::Some(_) = entry_bucket {
+                continue;
             }
 
-            last_bucket = b;
+            metric!(displacements);
+            let tombstone = matches!(entry_bucket, EntryBucket::Tombstone);
+            *entry_bucket = EntryBucket::Some(evicted);
+            return Ok((home, tombstone));
         }
 
-        unsafe {
-            ptr::write(last_bucket.as_mut(), EntryBucket::Some(bucket));
+        match mem::replace(EntryBucket::at_mut(table, home), EntryBucket::Some(evicted)) {
+            EntryBucket::Some(bucket) => Err(bucket),
+            _ => unreachable!(),
         }
-
-        InsertResult::Success
     }
 
-    pub fn lookup<'a, K, V, F>(
+    // the lookup of LCFS also stops on None, so it walks as FCFS
+    pub fn lookup<'a, K, V, Q, F>(
         table: &'a RawHashTable,
-        key: &K,
+        key: &Q,
         hash: u64,
-        mut offset: F,
-    ) -> Option<&'a mut EntryBucket<K, V>>
+        offset: F,
+    ) -> Option<&'a Bucket<K, V>>
     where
-        K: PartialEq,
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
         F: FnMut() -> usize,
     {
-        let hash_index = hash as usize & table.mask;
-
-        let first_bucket = table.buckets.as_ptr() as *mut u8 as *mut EntryBucket<K, V>;
-        let mut bucket = unsafe { &mut *first_bucket.add(hash_index) };
-
-        loop {
-            match bucket {
-                EntryBucket::None => {
-                    return None;
-                }
-                EntryBucket::Tombstone => {}
-                EntryBucket::Some(entry_bucket) => {
-                    if entry_bucket.hash == hash && entry_bucket.key == *key {
-                        return Some(bucket);
-                    }
-                }
-            }
-
-            let next_index = hash_index.wrapping_add(offset()) & table.mask;
-
-            if next_index == hash_index {
-                return None;
-            }
+        FCFS::lookup(table, key, hash, offset)
+    }
 
-            unsafe { bucket = &mut *first_bucket.add(next_index) }
-        }
+    pub fn lookup_mut<'a, K, V, Q, F>(
+        table: &'a mut RawHashTable,
+        key: &Q,
+        hash: u64,
+        offset: F,
+    ) -> Option<&'a mut Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+        F: FnMut() -> usize,
+    {
+        FCFS::lookup_mut(table, key, hash, offset)
     }
 
-    pub fn remove<K, V, F>(
-        table: &RawHashTable,
-        key: &K,
+    #[allow(clippy::result_unit_err)]
+    pub fn remove<K, V, Q, F>(
+        table: &mut RawHashTable,
+        key: &Q,
         hash: u64,
         offset: F,
         tombstone: bool,
     ) -> Result<EntryBucket<K, V>, ()>
     where
-        K: PartialEq,
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
         F: FnMut() -> usize,
     {
-        if tombstone {
-            if let Some(entry_bucket) = Self::lookup(table, key, hash, offset) {
-                match entry_bucket {
-                    EntryBucket::None => Err(()),
-                    EntryBucket::Some(_) => Ok(mem::replace(entry_bucket, EntryBucket::Tombstone)),
-                    EntryBucket::Tombstone => panic!(),
-                }
-            } else {
-                Err(())
-            }
+        FCFS::remove(table, key, hash, offset, tombstone)
+    }
+
+    pub fn remove_at<K, V>(
+        table: &mut RawHashTable,
+        index: usize,
+        tombstone: bool,
+    ) -> EntryBucket<K, V> {
+        // the lookup of LCFS also stops on None, so the same removal as FCFS keeps it
+        FCFS::remove_at(table, index, tombstone)
+    }
+
+    // the lookup of LCFS also stops on None, so the buckets are placed again as FCFS
+    pub fn rehash_in_place<K, V, G, F>(table: &mut RawHashTable, offset_of: G)
+    where
+        G: Fn(&K) -> F,
+        F: FnMut() -> usize,
+    {
+        FCFS::rehash_in_place::<K, V, G, F>(table, offset_of)
+    }
+}
+
+/// The LCFS entry strategy on the probe sequence `P`. The evicted buckets are moved along their
+/// own probe sequences, so the sequence missing some buckets grows the table earlier.
+pub struct Lcfs<P> {
+    probe: P,
+    tombstone: bool,
+}
+
+impl<P: ProbeSequence> Lcfs<P> {
+    /// If `tombstone` is false, the removal fills the hole by backward shift instead of leaving
+    /// a tombstone.
+    ///
+    /// # Panics
+    ///
+    /// As `Fcfs::with_probe`, it panics if `tombstone` is false on the probe sequence other than
+    /// the linear probing.
+    pub fn with_probe(probe: P, tombstone: bool) -> Self {
+        assert!(
+            tombstone || probe.contiguous(),
+            "backward shift needs a contiguous probe sequence"
+        );
+
+        Self { probe, tombstone }
+    }
+
+    fn offset<K: ?Sized + Hash>(&self, key: &K, mask: usize) -> impl FnMut() -> usize {
+        let mut probe = self.probe.probe(key, mask);
+
+        // the end of the sequence returns to the home, which stops the walk
+        move || probe.next().unwrap_or(0)
+    }
+}
+
+impl<P: ProbeSequence + Default> Default for Lcfs<P> {
+    fn default() -> Self {
+        Self::with_probe(P::default(), true)
+    }
+}
+
+impl<K: PartialEq + Hash, V, P: ProbeSequence + Default> EntryStrategy<K, Bucket<K, V>>
+    for Lcfs<P>
+{
+    fn insert(
+        &mut self,
+        table: &mut RawHashTable,
+        bucket: Bucket<K, V>,
+    ) -> InsertResult<Bucket<K, V>> {
+        let mask = table.mask;
+
+        LCFS::insert(table, |key| self.offset(key, mask), bucket)
+    }
+
+    fn lookup<'a, Q>(&self, table: &'a RawHashTable, key: &Q, hash: u64) -> Option<&'a Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        LCFS::lookup(table, key, hash, self.offset(key, table.mask))
+    }
+
+    fn lookup_mut<'a, Q>(
+        &self,
+        table: &'a mut RawHashTable,
+        key: &Q,
+        hash: u64,
+    ) -> Option<&'a mut Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        LCFS::lookup_mut(table, key, hash, self.offset(key, table.mask))
+    }
+
+    fn remove<Q>(
+        &mut self,
+        table: &mut RawHashTable,
+        key: &Q,
+        hash: u64,
+    ) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let offset = self.offset(key, table.mask);
+        let entry_bucket = LCFS::remove(table, key, hash, offset, self.tombstone)?;
+
+        if let EntryBucket::Some(bucket) = entry_bucket {
+            Ok(bucket)
         } else {
-            todo!("Backshift is not implemented now.")
+            unreachable!()
         }
     }
+
+    fn entry<Q>(&self, table: &RawHashTable, key: &Q, hash: u64) -> EntryResult
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        LCFS::entry::<K, V, Q, _>(table, key, hash, self.offset(key, table.mask))
+    }
+
+    fn insert_at(
+        &mut self,
+        table: &mut RawHashTable,
+        _: usize,
+        bucket: Bucket<K, V>,
+    ) -> Result<(usize, bool), Bucket<K, V>> {
+        let mask = table.mask;
+
+        // the bucket takes its home instead of the free bucket found by `entry`
+        LCFS::insert_at(table, |key| self.offset(key, mask), bucket)
+    }
+
+    fn remove_at(&mut self, table: &mut RawHashTable, index: usize) -> Bucket<K, V> {
+        let entry_bucket = LCFS::remove_at(table, index, self.tombstone);
+
+        if let EntryBucket::Some(bucket) = entry_bucket {
+            bucket
+        } else {
+            unreachable!()
+        }
+    }
+
+    fn rehash_in_place(&mut self, table: &mut RawHashTable) {
+        let mask = table.mask;
+
+        LCFS::rehash_in_place::<K, V, _, _>(table, |key| self.offset(key, mask));
+    }
+
+    // the lookup of LCFS walks as FCFS
+    fn probe_length(&self, table: &RawHashTable, index: usize) -> usize {
+        if let EntryBucket::<K, V>::Some(bucket) = EntryBucket::at(table, index) {
+            FCFS::probe_length(
+                table,
+                bucket.hash,
+                index,
+                self.offset(&bucket.key, table.mask),
+            )
+        } else {
+            unreachable!()
+        }
+    }
+
+    fn miss_length(&self, table: &RawHashTable, home: usize) -> usize {
+        // the home stands in for the absent key, as FCFS
+        FCFS::miss_length::<K, V, _>(table, home, self.offset(&home, table.mask))
+    }
 }
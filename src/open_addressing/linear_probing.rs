@@ -1,138 +1,142 @@
-use std::ptr;
+use std::borrow::Borrow;
+use std::hash::Hash;
 
-use crate::{Entry, InsertResult, RawHashTable};
+use crate::{EntryResult, EntryStrategy, InsertResult, RawHashTable};
 
-use super::{Bucket, EntryBucket, FCFS, LCFS};
+use super::{Bucket, EntryBucket, Fcfs, Lcfs, ProbeSequence, RobinHood};
 
-pub struct FcfsLinearProbing {
-    step: usize,
-    tombstone: bool,
-}
+/// The probe sequence visiting the next bucket on each step
+#[derive(Default)]
+pub struct LinearProbing;
 
-impl Default for FcfsLinearProbing {
-    fn default() -> Self {
-        Self {
-            step: 1,
-            tombstone: true,
-        }
-    }
+pub struct LinearProbe {
+    offset: usize,
 }
 
-impl<K: PartialEq, V> Entry<K, Bucket<K, V>> for FcfsLinearProbing {
-    fn insert(&mut self, table: &RawHashTable, bucket: Bucket<K, V>) -> InsertResult<Bucket<K, V>> {
-        let mut step = 0;
-
-        let offset = || {
-            step += self.step;
-            step
-        };
-
-        if let Ok(entry_bucket) = FCFS::lookup(table, &bucket.key, bucket.hash, offset) {
-            match entry_bucket {
-                EntryBucket::Some(_) => InsertResult::AlreadyExist(bucket),
-                EntryBucket::None | EntryBucket::Tombstone => {
-                    unsafe { ptr::write(entry_bucket, EntryBucket::Some(bucket)) };
-                    InsertResult::Success
-                }
-            }
-        } else {
-            InsertResult::Full(bucket)
-        }
-    }
-
-    fn lookup<'a>(&self, table: &'a RawHashTable, key: &K, hash: u64) -> Option<&'a Bucket<K, V>> {
-        let mut step = 0;
+impl Iterator for LinearProbe {
+    type Item = usize;
 
-        let offset = || {
-            step += self.step;
-            step
-        };
-
-        if let Ok(entry_bucket) = FCFS::lookup(table, key, hash, offset) {
-            match entry_bucket {
-                EntryBucket::None => None,
-                EntryBucket::Some(bucket) => Some(bucket),
-                EntryBucket::Tombstone => None,
-            }
-        } else {
-            None
-        }
+    fn next(&mut self) -> Option<Self::Item> {
+        self.offset = self.offset.wrapping_add(1);
+        Some(self.offset)
     }
+}
 
-    fn remove(&mut self, table: &RawHashTable, key: &K, hash: u64) -> Result<Bucket<K, V>, ()> {
-        let mut step = 0;
-
-        let offset = || {
-            step += self.step;
-            step
-        };
+impl ProbeSequence for LinearProbing {
+    type Probe = LinearProbe;
 
-        let entry_bucket = FCFS::remove(table, key, hash, offset, self.tombstone)?;
+    fn probe<K: ?Sized + Hash>(&self, _: &K, _: usize) -> Self::Probe {
+        LinearProbe { offset: 0 }
+    }
 
-        match entry_bucket {
-            EntryBucket::Some(bucket) => Ok(bucket),
-            _ => Err(()),
-        }
+    fn contiguous(&self) -> bool {
+        true
     }
 }
 
-pub struct LcfsLinearProbing {
-    step: usize,
-    tombstone: bool,
-}
+pub type FcfsLinearProbing = Fcfs<LinearProbing>;
+pub type LcfsLinearProbing = Lcfs<LinearProbing>;
 
-impl Default for LcfsLinearProbing {
-    fn default() -> Self {
-        Self {
-            step: 1,
-            tombstone: true,
-        }
+impl Fcfs<LinearProbing> {
+    pub fn new(tombstone: bool) -> Self {
+        Self::with_probe(LinearProbing, tombstone)
     }
 }
 
-impl<K: PartialEq, V> Entry<K, Bucket<K, V>> for LcfsLinearProbing {
-    fn insert(&mut self, table: &RawHashTable, bucket: Bucket<K, V>) -> InsertResult<Bucket<K, V>> {
-        let mut step = 0;
-
-        let offset = || {
-            step += self.step;
-            step
-        };
+impl Lcfs<LinearProbing> {
+    pub fn new(tombstone: bool) -> Self {
+        Self::with_probe(LinearProbing, tombstone)
+    }
+}
 
-        LCFS::insert(table, offset, bucket, self.tombstone)
+/// Robin Hood hashing always uses backward shift for the removal, so it does not have tombstones.
+#[derive(Default)]
+pub struct RobinHoodLinearProbing;
+
+impl<K: PartialEq, V> EntryStrategy<K, Bucket<K, V>> for RobinHoodLinearProbing {
+    fn insert(
+        &mut self,
+        table: &mut RawHashTable,
+        bucket: Bucket<K, V>,
+    ) -> InsertResult<Bucket<K, V>> {
+        RobinHood::insert(table, bucket)
     }
 
-    fn lookup<'a>(&self, table: &'a RawHashTable, key: &K, hash: u64) -> Option<&'a Bucket<K, V>> {
-        let mut step = 0;
+    fn lookup<'a, Q>(&self, table: &'a RawHashTable, key: &Q, hash: u64) -> Option<&'a Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        RobinHood::lookup(table, key, hash)
+    }
 
-        let offset = || {
-            step += self.step;
-            step
-        };
+    fn lookup_mut<'a, Q>(
+        &self,
+        table: &'a mut RawHashTable,
+        key: &Q,
+        hash: u64,
+    ) -> Option<&'a mut Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        RobinHood::lookup_mut(table, key, hash)
+    }
 
-        let entry_bucket = LCFS::lookup(table, key, hash, offset)?;
+    fn remove<Q>(
+        &mut self,
+        table: &mut RawHashTable,
+        key: &Q,
+        hash: u64,
+    ) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let entry_bucket = RobinHood::remove(table, key, hash)?;
 
         if let EntryBucket::Some(bucket) = entry_bucket {
-            Some(&*bucket)
+            Ok(bucket)
         } else {
             unreachable!()
         }
     }
 
-    fn remove(&mut self, table: &RawHashTable, key: &K, hash: u64) -> Result<Bucket<K, V>, ()> {
-        let mut step = 0;
-
-        let offset = || {
-            step += self.step;
-            step
-        };
+    fn entry<Q>(&self, table: &RawHashTable, key: &Q, hash: u64) -> EntryResult
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        RobinHood::entry::<K, V, Q>(table, key, hash)
+    }
 
-        let entry_bucket = LCFS::remove(table, key, hash, offset, self.tombstone)?;
+    fn insert_at(
+        &mut self,
+        table: &mut RawHashTable,
+        index: usize,
+        bucket: Bucket<K, V>,
+    ) -> Result<(usize, bool), Bucket<K, V>> {
+        // no tombstone is left by the removal of Robin Hood
+        Ok((RobinHood::insert_at(table, index, bucket), false))
+    }
 
-        if let EntryBucket::Some(bucket) = entry_bucket {
-            Ok(bucket)
+    fn remove_at(&mut self, table: &mut RawHashTable, index: usize) -> Bucket<K, V> {
+        if let EntryBucket::Some(bucket) = RobinHood::remove_at(table, index) {
+            bucket
         } else {
             unreachable!()
         }
     }
+
+    fn rehash_in_place(&mut self, _: &mut RawHashTable) {
+        unreachable!()
+    }
+
+    fn probe_length(&self, table: &RawHashTable, index: usize) -> usize {
+        RobinHood::probe_length::<K, V>(table, index)
+    }
+
+    fn miss_length(&self, table: &RawHashTable, home: usize) -> usize {
+        RobinHood::miss_length::<K, V>(table, home)
+    }
 }
@@ -1,31 +1,71 @@
 use std::alloc::{alloc, dealloc, Layout};
+use std::borrow::Borrow;
 use std::collections::hash_map::DefaultHasher;
 use std::fmt::Debug;
-use std::hash::{BuildHasher, BuildHasherDefault, Hasher};
+use std::hash::{BuildHasher, BuildHasherDefault};
 use std::marker::PhantomData;
 use std::{hash::Hash, ptr::NonNull};
 use std::{mem, ptr};
 
-use crate::{Entry, HashMap, HashTable, InsertResult, RawHashTable, INITIAL_SIZE, LOAD_FACTOR};
+use crate::{
+    size_for, Entry, EntryResult, EntryStrategy, HashMap, HashTable, InsertResult, OccupiedEntry,
+    RawHashTable, VacantEntry, INITIAL_SIZE, LOAD_FACTOR,
+};
 
 mod double_hashing;
+mod entry;
 mod fcfs;
+mod iter;
 mod lcfs;
 mod linear_probing;
 mod quadratic_probing;
-
-pub use fcfs::FCFS;
-pub use lcfs::LCFS;
+mod random_probing;
+mod robin_hood;
+mod set;
+mod stats;
+
+pub use entry::{Occupied, Vacant};
+pub use fcfs::{Fcfs, FCFS};
+pub use iter::{IntoIter, Iter, IterMut};
+pub use lcfs::{Lcfs, LCFS};
+pub use robin_hood::RobinHood;
+pub use set::OpenAddressingHashSet;
+pub use stats::{ProbeStats, Stats};
 
 pub use double_hashing::FcfsDoubleHashing;
+pub use double_hashing::LcfsDoubleHashing;
+pub use double_hashing::{DoubleHashing, DoubleHashingProbe};
 pub use linear_probing::FcfsLinearProbing;
 pub use linear_probing::LcfsLinearProbing;
+pub use linear_probing::RobinHoodLinearProbing;
+pub use linear_probing::{LinearProbe, LinearProbing};
 pub use quadratic_probing::FcfsQuadraticProbing;
+pub use quadratic_probing::LcfsQuadraticProbing;
+pub use quadratic_probing::QuadraticMode;
+pub use quadratic_probing::{QuadraticProbe, QuadraticProbing};
+pub use random_probing::FcfsRandomProbing;
+pub use random_probing::LcfsRandomProbing;
+pub use random_probing::{RandomProbe, RandomProbing};
+
+/// The probe sequence of open addressing, which gives the offsets from the home bucket on each
+/// step. The walk stops when the sequence returns to the home(offset 0 modulo the size) or ends.
+pub trait ProbeSequence {
+    type Probe: Iterator<Item = usize>;
+
+    fn probe<K: ?Sized + Hash>(&self, key: &K, mask: usize) -> Self::Probe;
+
+    // whether the sequence visits the next bucket on each step, which the backward shift needs to
+    // look up only the cluster after the hole(the removal leaves tombstones on the others)
+    fn contiguous(&self) -> bool {
+        false
+    }
+}
 
+// the value is stored inline, so `Box<V>` can be used for the large value not to be moved
 pub struct Bucket<K, V> {
     key: K,
     hash: u64,
-    value: Box<V>,
+    value: V,
 }
 
 pub enum EntryBucket<K, V> {
@@ -35,7 +75,7 @@ pub enum EntryBucket<K, V> {
 }
 
 impl<K, V> EntryBucket<K, V> {
-    pub fn alloc(size: usize) -> *mut Self {
+    pub(crate) fn alloc(size: usize) -> *mut Self {
         let layout = match Layout::array::<Self>(size) {
             Ok(layout) => layout,
             Err(_) => panic!("Cannot initialize EntryBuckets"),
@@ -53,7 +93,20 @@ impl<K, V> EntryBucket<K, V> {
         }
     }
 
-    pub fn dealloc(ptr: NonNull<u8>, size: usize) {
+    // the index is masked, so it never reaches out of the table
+    pub(crate) fn at(table: &RawHashTable, index: usize) -> &Self {
+        let first_bucket = table.buckets.as_ptr() as *const Self;
+
+        unsafe { &*first_bucket.add(index & table.mask) }
+    }
+
+    pub(crate) fn at_mut(table: &mut RawHashTable, index: usize) -> &mut Self {
+        let first_bucket = table.buckets.as_ptr() as *mut Self;
+
+        unsafe { &mut *first_bucket.add(index & table.mask) }
+    }
+
+    pub(crate) fn dealloc(ptr: NonNull<u8>, size: usize) {
         let layout = match Layout::array::<Self>(size) {
             Ok(layout) => layout,
             Err(_) => panic!("Cannot initialize EntryBuckets"),
@@ -67,22 +120,48 @@ impl<K, V> EntryBucket<K, V> {
 
 pub struct OpenAddressingHashTable<K, V, E, S = BuildHasherDefault<DefaultHasher>>
 where
-    K: PartialEq + Hash + Clone,
-    E: Entry<K, Bucket<K, V>>,
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
     S: BuildHasher,
 {
     hashtable: HashTable<K, V, S, E, Bucket<K, V>>,
 }
 
-impl<K: PartialEq + Hash + Clone, V, E: Entry<K, Bucket<K, V>>, S: BuildHasher> Drop
+impl<K: PartialEq + Hash, V, E: EntryStrategy<K, Bucket<K, V>>, S: BuildHasher> Drop
     for OpenAddressingHashTable<K, V, E, S>
 {
     fn drop(&mut self) {
+        let first_bucket = self.hashtable.inner.buckets.as_ptr() as *mut EntryBucket<K, V>;
+
+        for index in 0..=self.hashtable.inner.mask {
+            unsafe { ptr::drop_in_place(first_bucket.add(index)) };
+        }
+
         EntryBucket::<K, V>::dealloc(self.hashtable.inner.buckets, self.hashtable.inner.mask + 1);
     }
 }
 
-impl<K: PartialEq + Hash + Clone + Debug, V: Debug, E: Entry<K, Bucket<K, V>>>
+// the table owns the buckets behind the raw pointer like `Vec<EntryBucket<K, V>>`, and the lookup
+// by `&self` only reads them through `EntryStrategy::lookup`, never creating `&mut` to them
+unsafe impl<K, V, E, S> Send for OpenAddressingHashTable<K, V, E, S>
+where
+    K: PartialEq + Hash + Send,
+    V: Send,
+    E: EntryStrategy<K, Bucket<K, V>> + Send,
+    S: BuildHasher + Send,
+{
+}
+
+unsafe impl<K, V, E, S> Sync for OpenAddressingHashTable<K, V, E, S>
+where
+    K: PartialEq + Hash + Sync,
+    V: Sync,
+    E: EntryStrategy<K, Bucket<K, V>> + Sync,
+    S: BuildHasher + Sync,
+{
+}
+
+impl<K: PartialEq + Hash + Debug, V: Debug, E: EntryStrategy<K, Bucket<K, V>>>
     OpenAddressingHashTable<K, V, E>
 {
     pub fn print(&self) {
@@ -109,8 +188,8 @@ impl<K: PartialEq + Hash + Clone + Debug, V: Debug, E: Entry<K, Bucket<K, V>>>
 
 impl<K, V, E, S> OpenAddressingHashTable<K, V, E, S>
 where
-    K: PartialEq + Hash + Clone,
-    E: Entry<K, Bucket<K, V>>,
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
     S: BuildHasher,
 {
     pub fn new_with_properties(hasher: S, entry: E, initial_size: usize, load_factor: f32) -> Self {
@@ -121,7 +200,10 @@ where
                 mask: initial_size - 1,
             },
             count: 0,
+            tombstones: 0,
             load_factor,
+            shrink_load_factor: 0.0,
+            tombstone_factor: 1.0,
             entry: Box::new(entry),
             _marker: PhantomData,
         };
@@ -129,21 +211,49 @@ where
         Self { hashtable }
     }
 
-    fn hash_one(&self, key: &K) -> u64 {
-        let mut hasher = self.hashtable.hasher.build_hasher();
-        key.hash(&mut hasher);
-        hasher.finish()
+    /// Shrink the table automatically when the load drops under `shrink_load_factor` by
+    /// removals. It is disabled by default(`0.0`), and `SHRINK_LOAD_FACTOR` is a reasonable value.
+    pub fn set_shrink_load_factor(&mut self, shrink_load_factor: f32) {
+        self.hashtable.shrink_load_factor = shrink_load_factor;
+    }
+
+    /// Purge the tombstones in place when they take more than `tombstone_factor` of the table
+    /// by removals. It is disabled by default(`1.0`), and `TOMBSTONE_FACTOR` is a reasonable value.
+    pub fn set_tombstone_factor(&mut self, tombstone_factor: f32) {
+        self.hashtable.tombstone_factor = tombstone_factor;
+    }
+
+    /// Purge all tombstones without allocating a new table. The tombstones become None, and each
+    /// bucket is placed again along its probe sequence, swapping with the buckets not placed yet.
+    pub fn rehash_in_place(&mut self) {
+        if self.hashtable.tombstones == 0 {
+            return;
+        }
+
+        self.hashtable
+            .entry
+            .rehash_in_place(&mut self.hashtable.inner);
+
+        self.hashtable.tombstones = 0;
+    }
+
+    fn hash_one<Q: ?Sized + Hash>(&self, key: &Q) -> u64 {
+        metric!(hashes);
+        self.hashtable.hasher.hash_one(key)
     }
 
     fn insert_bucket(&mut self, bucket: Bucket<K, V>) -> Result<(), V> {
-        let result = self.hashtable.entry.insert(&self.hashtable.inner, bucket);
+        let result = self
+            .hashtable
+            .entry
+            .insert(&mut self.hashtable.inner, bucket);
 
         match result {
             InsertResult::Success => {
                 self.hashtable.count += 1;
                 Ok(())
             }
-            InsertResult::AlreadyExist(bucket) => Err(*bucket.value),
+            InsertResult::AlreadyExist(bucket) => Err(bucket.value),
             InsertResult::Full(bucket) => {
                 self.resize((self.hashtable.inner.mask + 1) << 1);
                 self.insert_bucket(bucket)
@@ -151,6 +261,101 @@ where
         }
     }
 
+    // whether one more bucket would exceed the load factor, counting the tombstones as taken
+    fn at_load_limit(&self) -> bool {
+        let size = self.hashtable.inner.mask + 1;
+
+        self.hashtable.count + self.hashtable.tombstones
+            >= (size as f32 * self.hashtable.load_factor) as usize
+    }
+
+    // grow the table before an insertion, or purge the tombstones in place if they dominate
+    fn reserve_one(&mut self) {
+        if self.at_load_limit() {
+            if self.hashtable.tombstones > self.hashtable.count {
+                self.rehash_in_place();
+            } else {
+                self.resize((self.hashtable.inner.mask + 1) << 1);
+            }
+        }
+    }
+
+    // shrink the table to the half load of the load factor if it gets sparse
+    fn shrink_if_sparse(&mut self) {
+        let size = self.hashtable.inner.mask + 1;
+
+        if size > INITIAL_SIZE
+            && (self.hashtable.count as f32) < size as f32 * self.hashtable.shrink_load_factor
+        {
+            self.resize(size_for(
+                self.hashtable.count * 2,
+                self.hashtable.load_factor,
+            ));
+        }
+    }
+
+    // find the bucket of the key, making room for it only if the key does not exist
+    fn find_or_reserve(&mut self, key: &K, hash: u64) -> EntryResult {
+        loop {
+            match self.hashtable.entry.entry(&self.hashtable.inner, key, hash) {
+                EntryResult::None(_) if self.at_load_limit() => self.reserve_one(),
+                EntryResult::Full => self.resize((self.hashtable.inner.mask + 1) << 1),
+                result => return result,
+            }
+        }
+    }
+
+    // insert the bucket on `EntryResult::None(index)`, which may reuse a tombstone
+    pub(crate) fn insert_at(&mut self, index: usize, bucket: Bucket<K, V>) -> usize {
+        let hashtable = &mut self.hashtable;
+        let result = hashtable
+            .entry
+            .insert_at(&mut hashtable.inner, index, bucket);
+
+        let (placed, tombstone) = match result {
+            Ok(placed) => placed,
+            Err(bucket) => {
+                // the bucket is not placed, so find its entry again on the larger table
+                self.resize((self.hashtable.inner.mask + 1) << 1);
+
+                return match self.find_or_reserve(&bucket.key, bucket.hash) {
+                    EntryResult::None(index) => self.insert_at(index, bucket),
+                    EntryResult::Some(_) | EntryResult::Full => unreachable!(),
+                };
+            }
+        };
+
+        let hashtable = &mut self.hashtable;
+
+        if tombstone {
+            hashtable.tombstones -= 1;
+        }
+
+        hashtable.count += 1;
+        placed
+    }
+
+    // remove the bucket on `EntryResult::Some(index)`, which may leave a tombstone
+    pub(crate) fn remove_at(&mut self, index: usize) -> Bucket<K, V> {
+        let hashtable = &mut self.hashtable;
+        let bucket = hashtable.entry.remove_at(&mut hashtable.inner, index);
+
+        if let EntryBucket::Tombstone = EntryBucket::<K, V>::at(&hashtable.inner, index) {
+            hashtable.tombstones += 1;
+        }
+
+        hashtable.count -= 1;
+        self.shrink_if_sparse();
+
+        if self.hashtable.tombstones as f32
+            > (self.hashtable.inner.mask + 1) as f32 * self.hashtable.tombstone_factor
+        {
+            self.rehash_in_place();
+        }
+
+        bucket
+    }
+
     fn resize(&mut self, new_size: usize) {
         let new_inner = RawHashTable {
             buckets: NonNull::new(EntryBucket::<K, V>::alloc(new_size) as *mut u8).unwrap(),
@@ -158,14 +363,17 @@ where
         };
 
         let old_inner = mem::replace(&mut self.hashtable.inner, new_inner);
+        metric!(resizes);
 
         self.hashtable.count = 0;
+        self.hashtable.tombstones = 0;
         for index in 0..=old_inner.mask {
             let entry_bucket = unsafe {
                 ptr::read((old_inner.buckets.as_ptr() as *const EntryBucket<K, V>).add(index))
             };
 
             if let EntryBucket::Some(bucket) = entry_bucket {
+                metric!(bytes_moved, mem::size_of::<Bucket<K, V>>());
                 assert!(self.insert_bucket(bucket).is_ok());
             }
         }
@@ -176,8 +384,8 @@ where
 
 impl<K, V, E, S> HashMap<K, V, S> for OpenAddressingHashTable<K, V, E, S>
 where
-    K: PartialEq + Hash + Clone,
-    E: Entry<K, Bucket<K, V>>,
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
     S: BuildHasher + Default,
 {
     fn new() -> Self {
@@ -188,43 +396,160 @@ where
         Self::new_with_properties(hasher, E::default(), INITIAL_SIZE, LOAD_FACTOR)
     }
 
-    fn insert(&mut self, key: &K, value: V) -> Result<(), V> {
-        let hash = self.hash_one(key);
+    fn with_capacity(capacity: usize) -> Self {
+        Self::new_with_properties(
+            S::default(),
+            E::default(),
+            size_for(capacity, LOAD_FACTOR),
+            LOAD_FACTOR,
+        )
+    }
 
-        let bucket = Bucket {
-            key: key.clone(),
-            hash,
-            value: Box::new(value),
-        };
+    fn insert(&mut self, key: K, value: V) -> Result<(), V> {
+        let hash = self.hash_one(&key);
 
-        if self.hashtable.count
-            >= ((self.hashtable.inner.mask + 1) as f32 * self.hashtable.load_factor) as usize
-        {
-            self.resize((self.hashtable.inner.mask + 1) << 1);
-        }
+        match self.find_or_reserve(&key, hash) {
+            EntryResult::Some(_) => Err(value),
+            EntryResult::None(index) => {
+                let bucket = Bucket { key, hash, value };
 
-        self.insert_bucket(bucket)
+                self.insert_at(index, bucket);
+                Ok(())
+            }
+            EntryResult::Full => unreachable!(),
+        }
     }
 
-    fn lookup(&self, key: &K) -> Option<&V> {
+    fn lookup<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
         let hash = self.hash_one(key);
 
         let result = self
             .hashtable
             .entry
-            .lookup(&self.hashtable.inner, &key, hash)?;
+            .lookup(&self.hashtable.inner, key, hash)?;
 
-        Some(result.value.as_ref())
+        Some(&result.value)
     }
 
-    fn remove(&mut self, key: &K) -> Result<V, ()> {
+    fn lookup_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
         let hash = self.hash_one(key);
 
-        let bucket = self
+        let result = self
             .hashtable
             .entry
-            .remove(&mut self.hashtable.inner, key, hash)?;
+            .lookup_mut(&mut self.hashtable.inner, key, hash)?;
+
+        Some(&mut result.value)
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let hash = self.hash_one(key);
+
+        match self.hashtable.entry.entry(&self.hashtable.inner, key, hash) {
+            EntryResult::Some(index) => Ok(self.remove_at(index).value),
+            EntryResult::None(_) | EntryResult::Full => Err(()),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.hashtable.count
+    }
+
+    fn capacity(&self) -> usize {
+        ((self.hashtable.inner.mask + 1) as f32 * self.hashtable.load_factor) as usize
+    }
+
+    fn tombstones(&self) -> usize {
+        self.hashtable.tombstones
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        // the tombstones also occupy the table, so purge them first if it makes enough room
+        if self.hashtable.count + self.hashtable.tombstones + additional > self.capacity() {
+            if self.hashtable.count + additional <= self.capacity() {
+                self.rehash_in_place();
+            } else {
+                let new_size = size_for(
+                    self.hashtable.count + additional,
+                    self.hashtable.load_factor,
+                );
+                self.resize(new_size.max(self.hashtable.inner.mask + 1));
+            }
+        }
+    }
+
+    fn shrink_to(&mut self, min_capacity: usize) {
+        let new_size = size_for(
+            self.hashtable.count.max(min_capacity),
+            self.hashtable.load_factor,
+        );
+
+        if new_size < self.hashtable.inner.mask + 1 {
+            self.resize(new_size);
+        }
+    }
+
+    fn insert_or_replace(&mut self, key: K, value: V) -> Option<V> {
+        self.entry(key).insert(value)
+    }
+
+    fn clear(&mut self) {
+        for index in 0..=self.hashtable.inner.mask {
+            // dropping the old bucket and leaving None
+            *EntryBucket::<K, V>::at_mut(&mut self.hashtable.inner, index) = EntryBucket::None;
+        }
+
+        self.hashtable.count = 0;
+        self.hashtable.tombstones = 0;
+    }
+
+    fn entry<'a>(
+        &'a mut self,
+        key: K,
+    ) -> Entry<impl OccupiedEntry<'a, Key = K, Value = V>, impl VacantEntry<'a, Key = K, Value = V>>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let hash = self.hash_one(&key);
+
+        match self.find_or_reserve(&key, hash) {
+            EntryResult::Some(index) => Entry::Occupied(Occupied { table: self, index }),
+            EntryResult::None(index) => Entry::Vacant(Vacant {
+                table: self,
+                key,
+                hash,
+                index,
+            }),
+            EntryResult::Full => unreachable!(),
+        }
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        Iter::new(&self.hashtable.inner)
+    }
 
-        Ok(*bucket.value)
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (&'a K, &'a mut V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        IterMut::new(&mut self.hashtable.inner)
     }
 }
@@ -1,73 +1,113 @@
-use std::ptr;
+use std::hash::Hash;
 
-use crate::{Entry, InsertResult, RawHashTable};
+use super::{Fcfs, Lcfs, ProbeSequence};
 
-use super::{Bucket, EntryBucket, FCFS};
+/// The offset from the home bucket on each probe of quadratic probing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuadraticMode {
+    /// `i^2`, which visits only a part of the power-of-two table
+    Classic,
+    /// `i(i + 1) / 2`, which visits every bucket of the power-of-two table
+    Triangular,
+    /// `+1^2, -1^2, +2^2, -2^2, ...`, which visits every bucket only on the prime size of `4k + 3`
+    Alternating,
+}
+
+impl QuadraticMode {
+    /// The offset from the home bucket on the `step`-th probe, where the home is the 0-th.
+    pub fn offset(&self, step: usize) -> usize {
+        match self {
+            QuadraticMode::Classic => step.wrapping_mul(step),
+            QuadraticMode::Triangular => {
+                // halve the even one first, not to overflow before the division
+                if step.is_multiple_of(2) {
+                    (step / 2).wrapping_mul(step + 1)
+                } else {
+                    step.wrapping_mul(step.div_ceil(2))
+                }
+            }
+            QuadraticMode::Alternating => {
+                let square = step.div_ceil(2).wrapping_mul(step.div_ceil(2));
+
+                if step.is_multiple_of(2) {
+                    square.wrapping_neg()
+                } else {
+                    square
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct QuadraticProbing {
+    mode: QuadraticMode,
+}
 
-pub struct FcfsQuadraticProbing {
-    tombstone: bool,
+impl QuadraticProbing {
+    pub fn new(mode: QuadraticMode) -> Self {
+        Self { mode }
+    }
 }
 
-impl Default for FcfsQuadraticProbing {
+impl Default for QuadraticProbing {
     fn default() -> Self {
-        Self { tombstone: true }
+        Self::new(QuadraticMode::Triangular)
     }
 }
 
-impl<K: PartialEq, V> Entry<K, Bucket<K, V>> for FcfsQuadraticProbing {
-    fn insert(&mut self, table: &RawHashTable, bucket: Bucket<K, V>) -> InsertResult<Bucket<K, V>> {
-        let mut step = 0;
-
-        let offset = || {
-            step += 1;
-            step * step
-        };
-
-        if let Ok(entry_bucket) = FCFS::lookup(table, &bucket.key, bucket.hash, offset) {
-            match entry_bucket {
-                EntryBucket::Some(_) => InsertResult::AlreadyExist(bucket),
-                EntryBucket::None | EntryBucket::Tombstone => {
-                    unsafe { ptr::write(entry_bucket, EntryBucket::Some(bucket)) };
-                    InsertResult::Success
-                }
-            }
+pub struct QuadraticProbe {
+    mode: QuadraticMode,
+    step: usize,
+    mask: usize,
+}
+
+impl Iterator for QuadraticProbe {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step += 1;
+
+        // the table has no more buckets to visit
+        if self.step > self.mask {
+            None
         } else {
-            InsertResult::Full(bucket)
+            Some(self.mode.offset(self.step))
         }
     }
+}
 
-    fn lookup<'a>(&self, table: &'a RawHashTable, key: &K, hash: u64) -> Option<&'a Bucket<K, V>> {
-        let mut step = 0;
-
-        let offset = || {
-            step += 1;
-            step * step
-        };
+impl ProbeSequence for QuadraticProbing {
+    type Probe = QuadraticProbe;
 
-        if let Ok(entry_bucket) = FCFS::lookup(table, key, hash, offset) {
-            match entry_bucket {
-                EntryBucket::None => None,
-                EntryBucket::Some(bucket) => Some(bucket),
-                EntryBucket::Tombstone => None,
-            }
-        } else {
-            None
+    fn probe<K: ?Sized + Hash>(&self, _: &K, mask: usize) -> Self::Probe {
+        QuadraticProbe {
+            mode: self.mode,
+            step: 0,
+            mask,
         }
     }
+}
 
-    fn remove(&mut self, table: &RawHashTable, key: &K, hash: u64) -> Result<Bucket<K, V>, ()> {
-        let mut step = 0;
+/// The removal always leaves a tombstone, since the backward shift needs the linear probing.
+pub type FcfsQuadraticProbing = Fcfs<QuadraticProbing>;
 
-        let offset = || {
-            step += 1;
-            step * step
-        };
+impl Fcfs<QuadraticProbing> {
+    pub fn new() -> Self {
+        Self::with_probe(QuadraticProbing::default(), true)
+    }
 
-        let entry_bucket = FCFS::remove(table, key, hash, offset, self.tombstone)?;
+    pub fn with_mode(mode: QuadraticMode) -> Self {
+        Self::with_probe(QuadraticProbing::new(mode), true)
+    }
+}
 
-        match entry_bucket {
-            EntryBucket::Some(bucket) => Ok(bucket),
-            _ => Err(()),
-        }
+/// LCFS grows the table when an evicted bucket misses the free buckets, so the triangular mode
+/// visiting every bucket suits it best.
+pub type LcfsQuadraticProbing = Lcfs<QuadraticProbing>;
+
+impl Lcfs<QuadraticProbing> {
+    pub fn new() -> Self {
+        Self::with_probe(QuadraticProbing::default(), true)
     }
 }
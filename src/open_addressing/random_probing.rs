@@ -0,0 +1,81 @@
+use std::hash::Hash;
+use std::{collections::hash_map::RandomState, hash::BuildHasher};
+
+use super::{Fcfs, Lcfs, ProbeSequence};
+
+/// The pseudorandom probe sequence by the linear congruential generator `x' = 5x + c`, whose period
+/// is the whole power-of-two table for any odd `c`(Hull-Dobell theorem). `c` is hashed from the
+/// key, so the keys on the same home do not share the sequence.
+pub struct RandomProbing<H = RandomState> {
+    hasher: H,
+}
+
+impl<H: BuildHasher> RandomProbing<H> {
+    /// The increment of the generator is hashed by `hasher`, which should be independent of the
+    /// hasher of the table.
+    pub fn with_hasher(hasher: H) -> Self {
+        Self { hasher }
+    }
+}
+
+impl<H: BuildHasher + Default> Default for RandomProbing<H> {
+    fn default() -> Self {
+        Self::with_hasher(H::default())
+    }
+}
+
+pub struct RandomProbe {
+    offset: usize,
+    increment: usize,
+    mask: usize,
+}
+
+impl Iterator for RandomProbe {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.offset = self.offset.wrapping_mul(5).wrapping_add(self.increment) & self.mask;
+        Some(self.offset)
+    }
+}
+
+impl<H: BuildHasher> ProbeSequence for RandomProbing<H> {
+    type Probe = RandomProbe;
+
+    fn probe<K: ?Sized + Hash>(&self, key: &K, mask: usize) -> Self::Probe {
+        metric!(hashes);
+        RandomProbe {
+            offset: 0,
+            increment: self.hasher.hash_one(key) as usize | 1,
+            mask,
+        }
+    }
+}
+
+/// The removal always leaves a tombstone, since the backward shift needs the linear probing.
+pub type FcfsRandomProbing<H = RandomState> = Fcfs<RandomProbing<H>>;
+pub type LcfsRandomProbing<H = RandomState> = Lcfs<RandomProbing<H>>;
+
+impl Fcfs<RandomProbing> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<H: BuildHasher> Fcfs<RandomProbing<H>> {
+    pub fn with_hasher(hasher: H) -> Self {
+        Self::with_probe(RandomProbing::with_hasher(hasher), true)
+    }
+}
+
+impl Lcfs<RandomProbing> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<H: BuildHasher> Lcfs<RandomProbing<H>> {
+    pub fn with_hasher(hasher: H) -> Self {
+        Self::with_probe(RandomProbing::with_hasher(hasher), true)
+    }
+}
@@ -0,0 +1,221 @@
+use std::{borrow::Borrow, mem, ptr};
+
+use crate::{EntryResult, InsertResult, RawHashTable};
+
+use super::{Bucket, EntryBucket};
+
+/// Robin Hood hashing on linear probing.
+///
+/// The bucket which is farther from its home(longer probe sequence length) takes the place, so
+/// the buckets on a cluster are sorted by their home. It makes an unsuccessful lookup stop as soon
+/// as it meets a bucket closer to its home than the key, and the removal shifts back the following
+/// buckets instead of leaving a tombstone.
+pub struct RobinHood;
+
+impl RobinHood {
+    // probe sequence length of the bucket with `hash` on `index`
+    fn distance(table: &RawHashTable, hash: u64, index: usize) -> usize {
+        index.wrapping_sub(hash as usize) & table.mask
+    }
+
+    // find the index of the key, or the index where the key should be placed
+    fn probe<K, V, Q>(table: &RawHashTable, key: &Q, hash: u64) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let hash_index = hash as usize & table.mask;
+        let first_bucket = table.buckets.as_ptr() as *const EntryBucket<K, V>;
+
+        for distance in 0..=table.mask {
+            let index = hash_index.wrapping_add(distance) & table.mask;
+            metric!(probes);
+
+            match unsafe { &*first_bucket.add(index) } {
+                EntryBucket::None => return Err(index),
+                EntryBucket::Some(entry_bucket) => {
+                    metric!(comparisons);
+
+                    if entry_bucket.hash == hash {
+                        metric!(key_eq_checks);
+
+                        if entry_bucket.key.borrow() == key {
+                            return Ok(index);
+                        }
+                    }
+
+                    if Self::distance(table, entry_bucket.hash, index) < distance {
+                        return Err(index);
+                    }
+                }
+                EntryBucket::Tombstone => unreachable!(),
+            }
+        }
+
+        // the table is full and every bucket is farther from its home than the key
+        Err(hash_index)
+    }
+
+    pub fn probe_length<K, V>(table: &RawHashTable, index: usize) -> usize {
+        if let EntryBucket::<K, V>::Some(bucket) = EntryBucket::at(table, index) {
+            Self::distance(table, bucket.hash, index) + 1
+        } else {
+            unreachable!()
+        }
+    }
+
+    // the unsuccessful lookup stops on None, or on the bucket closer to its home than the key
+    pub fn miss_length<K, V>(table: &RawHashTable, home: usize) -> usize {
+        for distance in 0..=table.mask {
+            let index = home.wrapping_add(distance) & table.mask;
+
+            match EntryBucket::<K, V>::at(table, index) {
+                EntryBucket::Some(bucket)
+                    if Self::distance(table, bucket.hash, index) >= distance => {}
+                _ => return distance + 1,
+            }
+        }
+
+        table.mask + 1
+    }
+
+    // find the end of the cluster from `index`, which is None
+    fn cluster_end<K, V>(table: &RawHashTable, index: usize) -> Option<usize> {
+        let mut last = index;
+
+        while let EntryBucket::Some(_) = EntryBucket::<K, V>::at(table, last) {
+            last = (last + 1) & table.mask;
+
+            if last == index {
+                return None;
+            }
+        }
+
+        Some(last)
+    }
+
+    pub fn insert<K, V>(
+        table: &mut RawHashTable,
+        bucket: Bucket<K, V>,
+    ) -> InsertResult<Bucket<K, V>>
+    where
+        K: PartialEq,
+    {
+        match Self::entry::<K, V, K>(table, &bucket.key, bucket.hash) {
+            EntryResult::Some(_) => InsertResult::AlreadyExist(bucket),
+            EntryResult::None(index) => {
+                Self::insert_at(table, index, bucket);
+                InsertResult::Success
+            }
+            EntryResult::Full => InsertResult::Full(bucket),
+        }
+    }
+
+    pub fn entry<K, V, Q>(table: &RawHashTable, key: &Q, hash: u64) -> EntryResult
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        match Self::probe::<K, V, Q>(table, key, hash) {
+            Ok(index) => EntryResult::Some(index),
+            Err(index) if Self::cluster_end::<K, V>(table, index).is_some() => {
+                EntryResult::None(index)
+            }
+            Err(_) => EntryResult::Full,
+        }
+    }
+
+    // shift the buckets from `index` to the end of the cluster by one, and place the bucket
+    pub fn insert_at<K, V>(table: &mut RawHashTable, index: usize, bucket: Bucket<K, V>) -> usize {
+        let first_bucket = table.buckets.as_ptr() as *mut EntryBucket<K, V>;
+        let mut last = Self::cluster_end::<K, V>(table, index).unwrap();
+
+        while last != index {
+            let prev = last.wrapping_sub(1) & table.mask;
+
+            unsafe {
+                ptr::write(first_bucket.add(last), ptr::read(first_bucket.add(prev)));
+            }
+
+            last = prev;
+        }
+
+        unsafe {
+            ptr::write(first_bucket.add(index), EntryBucket::Some(bucket));
+        }
+
+        index
+    }
+
+    pub fn lookup<'a, K, V, Q>(
+        table: &'a RawHashTable,
+        key: &Q,
+        hash: u64,
+    ) -> Option<&'a Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let index = Self::probe::<K, V, Q>(table, key, hash).ok()?;
+
+        match EntryBucket::at(table, index) {
+            EntryBucket::Some(bucket) => Some(bucket),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn lookup_mut<'a, K, V, Q>(
+        table: &'a mut RawHashTable,
+        key: &Q,
+        hash: u64,
+    ) -> Option<&'a mut Bucket<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let index = Self::probe::<K, V, Q>(table, key, hash).ok()?;
+
+        match EntryBucket::at_mut(table, index) {
+            EntryBucket::Some(bucket) => Some(bucket),
+            _ => unreachable!(),
+        }
+    }
+
+    #[allow(clippy::result_unit_err)]
+    pub fn remove<K, V, Q>(
+        table: &mut RawHashTable,
+        key: &Q,
+        hash: u64,
+    ) -> Result<EntryBucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let index = Self::probe::<K, V, Q>(table, key, hash).map_err(|_| ())?;
+
+        Ok(Self::remove_at(table, index))
+    }
+
+    pub fn remove_at<K, V>(table: &mut RawHashTable, mut hole: usize) -> EntryBucket<K, V> {
+        let first_bucket = table.buckets.as_ptr() as *mut EntryBucket<K, V>;
+
+        let removed = unsafe { mem::replace(&mut *first_bucket.add(hole), EntryBucket::None) };
+
+        // shift back the following buckets until the bucket on its home or None
+        loop {
+            let next = (hole + 1) & table.mask;
+
+            match unsafe { &*first_bucket.add(next) } {
+                EntryBucket::Some(entry_bucket)
+                    if Self::distance(table, entry_bucket.hash, next) > 0 =>
+                {
+                    unsafe { ptr::swap(first_bucket.add(hole), first_bucket.add(next)) };
+                    hole = next;
+                }
+                _ => break,
+            }
+        }
+
+        removed
+    }
+}
@@ -0,0 +1,162 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
+use std::iter::{FromIterator, Map};
+
+use crate::{size_for, EntryResult, EntryStrategy, HashMap, HashSet, INITIAL_SIZE, LOAD_FACTOR};
+
+use super::{Bucket, IntoIter, Iter, OpenAddressingHashTable};
+
+/// The set on `OpenAddressingHashTable`, whose buckets hold the keys only since the value is `()`.
+pub struct OpenAddressingHashSet<K, E, S = BuildHasherDefault<DefaultHasher>>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, ()>>,
+    S: BuildHasher,
+{
+    table: OpenAddressingHashTable<K, (), E, S>,
+}
+
+impl<K, E, S> OpenAddressingHashSet<K, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, ()>>,
+    S: BuildHasher,
+{
+    pub fn new_with_properties(hasher: S, entry: E, initial_size: usize, load_factor: f32) -> Self {
+        Self {
+            table: OpenAddressingHashTable::new_with_properties(
+                hasher,
+                entry,
+                initial_size,
+                load_factor,
+            ),
+        }
+    }
+}
+
+impl<K, E, S> HashSet<K, S> for OpenAddressingHashSet<K, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, ()>>,
+    S: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self::new_with_properties(S::default(), E::default(), INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    fn with_hasher(hasher: S) -> Self {
+        Self::new_with_properties(hasher, E::default(), INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::new_with_properties(
+            S::default(),
+            E::default(),
+            size_for(capacity, LOAD_FACTOR),
+            LOAD_FACTOR,
+        )
+    }
+
+    fn insert(&mut self, key: K) -> Result<(), K> {
+        let hash = self.table.hash_one(&key);
+
+        match self.table.find_or_reserve(&key, hash) {
+            EntryResult::Some(_) => Err(key),
+            EntryResult::None(index) => {
+                let bucket = Bucket {
+                    key,
+                    hash,
+                    value: (),
+                };
+
+                self.table.insert_at(index, bucket);
+                Ok(())
+            }
+            EntryResult::Full => unreachable!(),
+        }
+    }
+
+    fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        self.table.lookup(key).is_some()
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<K, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let hash = self.table.hash_one(key);
+        let hashtable = &self.table.hashtable;
+
+        match hashtable.entry.entry(&hashtable.inner, key, hash) {
+            EntryResult::Some(index) => Ok(self.table.remove_at(index).key),
+            EntryResult::None(_) | EntryResult::Full => Err(()),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    fn clear(&mut self) {
+        self.table.clear();
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = &'a K>
+    where
+        K: 'a,
+    {
+        self.into_iter()
+    }
+}
+
+impl<K, E, S> FromIterator<K> for OpenAddressingHashSet<K, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, ()>>,
+    S: BuildHasher + Default,
+{
+    // the duplicated keys are dropped
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = Self::new();
+
+        for key in iter {
+            let _ = set.insert(key);
+        }
+
+        set
+    }
+}
+
+impl<K, E, S> IntoIterator for OpenAddressingHashSet<K, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, ()>>,
+    S: BuildHasher,
+{
+    type Item = K;
+    type IntoIter = Map<IntoIter<K, (), E, S>, fn((K, ())) -> K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.table.into_iter().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K, E, S> IntoIterator for &'a OpenAddressingHashSet<K, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, ()>>,
+    S: BuildHasher,
+{
+    type Item = &'a K;
+    type IntoIter = Map<Iter<'a, K, ()>, fn((&'a K, &'a ())) -> &'a K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(&self.table.hashtable.inner).map(|(key, _)| key)
+    }
+}
@@ -0,0 +1,128 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::EntryStrategy;
+
+use super::{Bucket, EntryBucket, OpenAddressingHashTable};
+
+/// The distribution of the probe lengths, which are the numbers of the buckets visited by lookups.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProbeStats {
+    pub average: f64,
+    pub max: usize,
+    pub variance: f64,
+    /// `histogram[i]` is the number of the lookups visiting `i + 1` buckets.
+    pub histogram: Vec<usize>,
+}
+
+impl ProbeStats {
+    fn from_lengths(lengths: Vec<usize>) -> Self {
+        if lengths.is_empty() {
+            return Self::default();
+        }
+
+        let count = lengths.len() as f64;
+        let average = lengths.iter().sum::<usize>() as f64 / count;
+        let variance = lengths
+            .iter()
+            .map(|&length| (length as f64 - average).powi(2))
+            .sum::<f64>()
+            / count;
+        let max = *lengths.iter().max().unwrap();
+
+        let mut histogram = vec![0; max];
+        for length in lengths {
+            histogram[length - 1] += 1;
+        }
+
+        Self {
+            average,
+            max,
+            variance,
+            histogram,
+        }
+    }
+}
+
+/// The statistics of the layout of `OpenAddressingHashTable`, walked by the probe sequence of its
+/// entry strategy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    /// The lookups of every key on the table.
+    pub successful: ProbeStats,
+    /// The lookups of the absent keys, one from every bucket as the home.
+    ///
+    /// These are exact only for the probe sequences drawn from the home alone, as linear and
+    /// quadratic probing. Double hashing and random probing draw the sequence from the key itself,
+    /// so the home stands in for the absent key there, and the numbers are an approximation.
+    pub unsuccessful: ProbeStats,
+    /// `clusters[i]` is the number of the primary clusters of `i + 1` buckets, which are the runs
+    /// of the occupied buckets including tombstones.
+    pub clusters: Vec<usize>,
+    pub tombstone_ratio: f64,
+}
+
+impl<K, V, E, S> OpenAddressingHashTable<K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    pub fn stats(&self) -> Stats {
+        let inner = &self.hashtable.inner;
+        let entry = &self.hashtable.entry;
+        let size = inner.mask + 1;
+
+        let successful = (0..size)
+            .filter(|&index| matches!(EntryBucket::<K, V>::at(inner, index), EntryBucket::Some(_)))
+            .map(|index| entry.probe_length(inner, index))
+            .collect();
+
+        let unsuccessful = (0..size)
+            .map(|home| entry.miss_length(inner, home))
+            .collect();
+
+        Stats {
+            successful: ProbeStats::from_lengths(successful),
+            unsuccessful: ProbeStats::from_lengths(unsuccessful),
+            clusters: self.clusters(),
+            tombstone_ratio: self.hashtable.tombstones as f64 / size as f64,
+        }
+    }
+
+    fn clusters(&self) -> Vec<usize> {
+        let inner = &self.hashtable.inner;
+        let size = inner.mask + 1;
+        let is_none = |index| matches!(EntryBucket::<K, V>::at(inner, index), EntryBucket::None);
+
+        // start the walk after None, so that the cluster wrapping around is counted at once
+        let start = match (0..size).find(|&index| is_none(index)) {
+            Some(start) => start,
+            None => {
+                let mut clusters = vec![0; size];
+                clusters[size - 1] = 1;
+                return clusters;
+            }
+        };
+
+        let mut clusters = Vec::new();
+        let mut length = 0;
+
+        for distance in 1..=size {
+            if is_none((start + distance) & inner.mask) {
+                if length > 0 {
+                    if clusters.len() < length {
+                        clusters.resize(length, 0);
+                    }
+
+                    clusters[length - 1] += 1;
+                }
+
+                length = 0;
+            } else {
+                length += 1;
+            }
+        }
+
+        clusters
+    }
+}
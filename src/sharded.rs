@@ -0,0 +1,215 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::open_addressing::{Bucket, OpenAddressingHashTable};
+use crate::{
+    ConcurrentHashMap, ConcurrentHashMapMut, EntryStrategy, HashMap, INITIAL_SIZE, LOAD_FACTOR,
+};
+
+// the default number of the shards
+pub const SHARDS: usize = 16;
+
+type Shard<K, V, E, S> = RwLock<OpenAddressingHashTable<K, V, E, S>>;
+
+/// The map partitioning the keys over the shards of `OpenAddressingHashTable`, each of which is
+/// locked by `RwLock`. The shard is chosen by the top bits of the hash, since the table of the
+/// shard uses the bottom bits.
+pub struct ShardedHashMap<K, V, E, S = BuildHasherDefault<DefaultHasher>>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    hasher: S,
+    shards: Box<[Shard<K, V, E, S>]>,
+    bits: u32, // log2 of the number of the shards
+}
+
+impl<K, V, E, S> ShardedHashMap<K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher + Clone,
+{
+    /// `shards` should be a power of two, and each shard starts from `initial_size`.
+    pub fn new_with_properties(
+        hasher: S,
+        shards: usize,
+        initial_size: usize,
+        load_factor: f32,
+    ) -> Self {
+        assert!(
+            shards.is_power_of_two(),
+            "The number of the shards should be a power of two."
+        );
+
+        let shards: Box<[_]> = (0..shards)
+            .map(|_| {
+                RwLock::new(OpenAddressingHashTable::new_with_properties(
+                    hasher.clone(),
+                    E::default(),
+                    initial_size,
+                    load_factor,
+                ))
+            })
+            .collect();
+
+        Self {
+            hasher,
+            bits: shards.len().trailing_zeros(),
+            shards,
+        }
+    }
+
+    fn shard(&self, key: &K) -> &Shard<K, V, E, S> {
+        let hash = self.hasher.hash_one(key);
+
+        &self.shards[hash.rotate_left(self.bits) as usize & (self.shards.len() - 1)]
+    }
+}
+
+/// The guard of the value, holding the read lock of its shard.
+pub struct Ref<'a, K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    _guard: RwLockReadGuard<'a, OpenAddressingHashTable<K, V, E, S>>,
+    // the value is not moved while the lock is held
+    value: NonNull<V>,
+}
+
+impl<'a, K, V, E, S> Deref for Ref<'a, K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+/// The guard of the value, holding the write lock of its shard.
+pub struct RefMut<'a, K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    _guard: RwLockWriteGuard<'a, OpenAddressingHashTable<K, V, E, S>>,
+    // the value is not moved while the lock is held
+    value: NonNull<V>,
+}
+
+impl<'a, K, V, E, S> Deref for RefMut<'a, K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<'a, K, V, E, S> DerefMut for RefMut<'a, K, V, E, S>
+where
+    K: PartialEq + Hash,
+    E: EntryStrategy<K, Bucket<K, V>>,
+    S: BuildHasher,
+{
+    fn deref_mut(&mut self) -> &mut V {
+        unsafe { self.value.as_mut() }
+    }
+}
+
+impl<K, V, E, S> ConcurrentHashMap<K, V, S> for ShardedHashMap<K, V, E, S>
+where
+    K: PartialEq + Hash + Send + Sync,
+    V: Send + Sync,
+    E: EntryStrategy<K, Bucket<K, V>> + Send + Sync,
+    S: BuildHasher + Default + Clone + Send + Sync,
+{
+    type Guard<'a>
+        = Ref<'a, K, V, E, S>
+    where
+        Self: 'a;
+
+    fn new() -> Self {
+        Self::new_with_properties(S::default(), SHARDS, INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    fn with_hasher(hasher: S) -> Self {
+        Self::new_with_properties(hasher, SHARDS, INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    fn insert(&self, key: K, value: V) -> Result<(), V> {
+        self.shard(&key).write().unwrap().insert(key, value)
+    }
+
+    fn lookup(&self, key: &K) -> Option<Self::Guard<'_>> {
+        let guard = self.shard(key).read().unwrap();
+        let value = NonNull::from(guard.lookup(key)?);
+
+        Some(Ref {
+            _guard: guard,
+            value,
+        })
+    }
+
+    fn remove(&self, key: &K) -> Result<V, ()> {
+        self.shard(key).write().unwrap().remove(key)
+    }
+
+    fn insert_or_replace(&self, key: K, value: V) -> Option<V> {
+        self.shard(&key)
+            .write()
+            .unwrap()
+            .insert_or_replace(key, value)
+    }
+
+    fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().unwrap().len())
+            .sum()
+    }
+
+    fn clear(&self) {
+        for shard in self.shards.iter() {
+            shard.write().unwrap().clear();
+        }
+    }
+}
+
+impl<K, V, E, S> ConcurrentHashMapMut<K, V, S> for ShardedHashMap<K, V, E, S>
+where
+    K: PartialEq + Hash + Send + Sync,
+    V: Send + Sync,
+    E: EntryStrategy<K, Bucket<K, V>> + Send + Sync,
+    S: BuildHasher + Default + Clone + Send + Sync,
+{
+    type GuardMut<'a>
+        = RefMut<'a, K, V, E, S>
+    where
+        Self: 'a;
+
+    fn lookup_mut(&self, key: &K) -> Option<Self::GuardMut<'_>> {
+        let mut guard = self.shard(key).write().unwrap();
+        let value = NonNull::from(guard.lookup_mut(key)?);
+
+        Some(RefMut {
+            _guard: guard,
+            value,
+        })
+    }
+}
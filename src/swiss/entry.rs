@@ -0,0 +1,91 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{OccupiedEntry, VacantEntry};
+
+use super::{Bucket, SwissTable};
+
+pub struct Occupied<'a, K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    pub(crate) table: &'a mut SwissTable<K, V, S>,
+    pub(crate) index: usize,
+}
+
+impl<'a, K, V, S> OccupiedEntry<'a> for Occupied<'a, K, V, S>
+where
+    K: PartialEq + Hash + 'a,
+    V: 'a,
+    S: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        unsafe { &(*self.table.slot(self.index)).key }
+    }
+
+    fn get(&self) -> &V {
+        unsafe { &(*self.table.slot(self.index)).value }
+    }
+
+    fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut (*self.table.slot(self.index)).value }
+    }
+
+    fn into_mut(self) -> &'a mut V {
+        unsafe { &mut (*self.table.slot(self.index)).value }
+    }
+
+    fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    fn remove(self) -> V {
+        let bucket = self.table.take(self.index);
+        self.table.shrink_if_sparse();
+
+        bucket.value
+    }
+}
+
+pub struct Vacant<'a, K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    pub(crate) table: &'a mut SwissTable<K, V, S>,
+    pub(crate) key: K,
+    pub(crate) hash: u64,
+}
+
+impl<'a, K, V, S> VacantEntry<'a> for Vacant<'a, K, V, S>
+where
+    K: PartialEq + Hash + 'a,
+    V: 'a,
+    S: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        &self.key
+    }
+
+    fn into_key(self) -> K {
+        self.key
+    }
+
+    fn insert(self, value: V) -> &'a mut V {
+        let table = self.table;
+
+        let index = table.insert_bucket(Bucket {
+            key: self.key,
+            hash: self.hash,
+            value,
+        });
+
+        unsafe { &mut (*table.slot(index)).value }
+    }
+}
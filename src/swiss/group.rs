@@ -0,0 +1,131 @@
+// the control byte of the empty slot, and the removed slot(tombstone). The full slot has the H2
+// tag, the top 7 bits of the hash, so its top bit is always clear.
+pub(crate) const EMPTY: u8 = 0b1111_1111;
+pub(crate) const DELETED: u8 = 0b1000_0000;
+
+pub(crate) use imp::{Group, STRIDE, WIDTH};
+
+/// The set of the matched slots in a group, where the `i`-th slot is on the bit `i * STRIDE`.
+#[derive(Clone, Copy)]
+pub(crate) struct BitMask(u64);
+
+impl BitMask {
+    pub(crate) fn any(self) -> bool {
+        self.0 != 0
+    }
+
+    pub(crate) fn lowest(self) -> Option<usize> {
+        if self.any() {
+            Some(self.0.trailing_zeros() as usize / STRIDE)
+        } else {
+            None
+        }
+    }
+
+    // the number of the unmatched slots from the start of the group
+    pub(crate) fn trailing_zeros(self) -> usize {
+        self.lowest().unwrap_or(WIDTH)
+    }
+
+    // the number of the unmatched slots from the end of the group
+    pub(crate) fn leading_zeros(self) -> usize {
+        (self.0.leading_zeros() as usize - (64 - WIDTH * STRIDE)) / STRIDE
+    }
+}
+
+impl Iterator for BitMask {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bit = self.lowest()?;
+        self.0 &= self.0 - 1;
+
+        Some(bit)
+    }
+}
+
+// compare the 16 control bytes at once by SSE2
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+mod imp {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::*;
+
+    use super::{BitMask, EMPTY};
+
+    pub(crate) const WIDTH: usize = 16;
+    pub(crate) const STRIDE: usize = 1;
+
+    #[derive(Clone, Copy)]
+    pub(crate) struct Group(__m128i);
+
+    impl Group {
+        // `ptr` should be valid for `WIDTH` bytes
+        pub(crate) unsafe fn load(ptr: *const u8) -> Self {
+            Group(_mm_loadu_si128(ptr as *const __m128i))
+        }
+
+        pub(crate) fn match_byte(self, byte: u8) -> BitMask {
+            unsafe {
+                let cmp = _mm_cmpeq_epi8(self.0, _mm_set1_epi8(byte as i8));
+                BitMask(_mm_movemask_epi8(cmp) as u16 as u64)
+            }
+        }
+
+        pub(crate) fn match_empty(self) -> BitMask {
+            self.match_byte(EMPTY)
+        }
+
+        // the top bit is set on the empty and deleted bytes
+        pub(crate) fn match_empty_or_deleted(self) -> BitMask {
+            unsafe { BitMask(_mm_movemask_epi8(self.0) as u16 as u64) }
+        }
+    }
+}
+
+// compare the 8 control bytes at once on a word, which is portable
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+)))]
+mod imp {
+    use std::ptr;
+
+    use super::BitMask;
+
+    pub(crate) const WIDTH: usize = 8;
+    pub(crate) const STRIDE: usize = 8;
+
+    const LSB: u64 = 0x0101_0101_0101_0101;
+    const MSB: u64 = 0x8080_8080_8080_8080;
+
+    #[derive(Clone, Copy)]
+    pub(crate) struct Group(u64);
+
+    impl Group {
+        // `ptr` should be valid for `WIDTH` bytes
+        pub(crate) unsafe fn load(ptr: *const u8) -> Self {
+            Group(u64::from_le(ptr::read_unaligned(ptr as *const u64)))
+        }
+
+        // it may have the false positive on the byte next to the matched one, which is filtered
+        // out by comparing the keys
+        pub(crate) fn match_byte(self, byte: u8) -> BitMask {
+            let cmp = self.0 ^ (LSB * byte as u64);
+            BitMask(cmp.wrapping_sub(LSB) & !cmp & MSB)
+        }
+
+        // only the empty byte has the top two bits
+        pub(crate) fn match_empty(self) -> BitMask {
+            BitMask(self.0 & (self.0 << 1) & MSB)
+        }
+
+        pub(crate) fn match_empty_or_deleted(self) -> BitMask {
+            BitMask(self.0 & MSB)
+        }
+    }
+}
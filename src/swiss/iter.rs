@@ -0,0 +1,156 @@
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::ptr::{self, NonNull};
+
+use crate::RawHashTable;
+
+use super::{Bucket, SwissTable};
+
+// the index of the next full slot from `index`, by the control bytes
+fn next_full(ctrl: NonNull<u8>, size: usize, index: &mut usize) -> Option<usize> {
+    while *index < size {
+        let current = *index;
+        *index += 1;
+
+        if unsafe { *ctrl.as_ptr().add(current) } & 0x80 == 0 {
+            return Some(current);
+        }
+    }
+
+    None
+}
+
+/// Iterator over the full slots, found by the control bytes.
+pub struct Iter<'a, K, V> {
+    ctrl: NonNull<u8>,
+    buckets: *const Bucket<K, V>,
+    size: usize,
+    index: usize,
+    _marker: PhantomData<&'a Bucket<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    pub(crate) fn new(ctrl: NonNull<u8>, table: &'a RawHashTable) -> Self {
+        Self {
+            ctrl,
+            buckets: table.buckets.as_ptr() as *const Bucket<K, V>,
+            size: table.mask + 1,
+            index: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = next_full(self.ctrl, self.size, &mut self.index)?;
+        let bucket = unsafe { &*self.buckets.add(index) };
+
+        Some((&bucket.key, &bucket.value))
+    }
+}
+
+pub struct IterMut<'a, K, V> {
+    ctrl: NonNull<u8>,
+    buckets: *mut Bucket<K, V>,
+    size: usize,
+    index: usize,
+    _marker: PhantomData<&'a mut Bucket<K, V>>,
+}
+
+impl<'a, K, V> IterMut<'a, K, V> {
+    pub(crate) fn new(ctrl: NonNull<u8>, table: &'a mut RawHashTable) -> Self {
+        Self {
+            ctrl,
+            buckets: table.buckets.as_ptr() as *mut Bucket<K, V>,
+            size: table.mask + 1,
+            index: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = next_full(self.ctrl, self.size, &mut self.index)?;
+        let bucket = unsafe { &mut *self.buckets.add(index) };
+
+        Some((&bucket.key, &mut bucket.value))
+    }
+}
+
+/// Owning iterator, taking out the buckets from the table. The table still owns the arrays and
+/// frees them on drop.
+pub struct IntoIter<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    table: SwissTable<K, V, S>,
+    index: usize,
+}
+
+impl<K, V, S> Iterator for IntoIter<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let size = self.table.inner.mask + 1;
+        let index = next_full(self.table.ctrl, size, &mut self.index)?;
+
+        // the slot is marked empty not to be dropped with the table
+        self.table.set_ctrl(index, super::EMPTY);
+        let bucket = unsafe { ptr::read(self.table.slot(index)) };
+
+        Some((bucket.key, bucket.value))
+    }
+}
+
+impl<K, V, S> IntoIterator for SwissTable<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            table: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a SwissTable<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.ctrl, &self.inner)
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut SwissTable<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IterMut::new(self.ctrl, &mut self.inner)
+    }
+}
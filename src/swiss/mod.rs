@@ -0,0 +1,489 @@
+use std::alloc::{alloc, dealloc, Layout};
+use std::borrow::Borrow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, BuildHasherDefault};
+use std::marker::PhantomData;
+use std::{hash::Hash, ptr::NonNull};
+use std::{mem, ptr};
+
+use crate::{
+    size_for, Entry, HashMap, OccupiedEntry, RawHashTable, VacantEntry, INITIAL_SIZE, LOAD_FACTOR,
+};
+
+use group::{Group, DELETED, EMPTY, WIDTH};
+
+mod entry;
+mod group;
+mod iter;
+mod set;
+
+pub use entry::{Occupied, Vacant};
+pub use iter::{IntoIter, Iter, IterMut};
+pub use set::SwissHashSet;
+
+pub struct Bucket<K, V> {
+    key: K,
+    hash: u64,
+    value: V,
+}
+
+// the H2 tag on the control byte, which is the top 7 bits of the hash
+fn h2(hash: u64) -> u8 {
+    (hash >> 57) as u8
+}
+
+fn alloc_ctrl(size: usize) -> NonNull<u8> {
+    let layout = match Layout::array::<u8>(size + WIDTH) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Cannot initialize Control bytes"),
+    };
+
+    // allocate and init with empty slots
+    unsafe {
+        let raw = alloc(layout);
+        ptr::write_bytes(raw, EMPTY, size + WIDTH);
+
+        NonNull::new(raw).unwrap()
+    }
+}
+
+fn dealloc_ctrl(ptr: NonNull<u8>, size: usize) {
+    let layout = match Layout::array::<u8>(size + WIDTH) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Cannot initialize Control bytes"),
+    };
+
+    unsafe {
+        dealloc(ptr.as_ptr(), layout);
+    }
+}
+
+// the slots are initialized only where the control byte is full
+fn alloc_slots<K, V>(size: usize) -> NonNull<u8> {
+    let layout = match Layout::array::<Bucket<K, V>>(size) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Cannot initialize Slots"),
+    };
+
+    unsafe { NonNull::new(alloc(layout)).unwrap() }
+}
+
+fn dealloc_slots<K, V>(ptr: NonNull<u8>, size: usize) {
+    let layout = match Layout::array::<Bucket<K, V>>(size) {
+        Ok(layout) => layout,
+        Err(_) => panic!("Cannot initialize Slots"),
+    };
+
+    unsafe {
+        dealloc(ptr.as_ptr(), layout);
+    }
+}
+
+// the triangular probing over the groups, which visits every group on the power-of-two table
+struct ProbeSeq {
+    position: usize,
+    stride: usize,
+}
+
+impl ProbeSeq {
+    fn move_next(&mut self, mask: usize) {
+        self.stride += WIDTH;
+        self.position = (self.position + self.stride) & mask;
+    }
+}
+
+/// SwissTable keeps a control byte for each slot on the separate array, which is the H2 tag(the
+/// top 7 bits of the hash) of the full slot, or the empty/deleted marker. The lookup matches the
+/// H2 tags of a group of the control bytes at once(16 by SSE2, or 8 on a word as the portable
+/// fallback), so the keys are rarely compared in vain and the probe stops at the group having an
+/// empty slot.
+///
+/// The first group of the control bytes is mirrored after the last one, so a group can be loaded
+/// from any slot. The table is not smaller than a group.
+pub struct SwissTable<K, V, S = BuildHasherDefault<DefaultHasher>>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    hasher: S,
+    ctrl: NonNull<u8>,
+    inner: RawHashTable,
+    count: usize,      // the number of live buckets
+    tombstones: usize, // the number of deleted control bytes, which also occupy the table
+    load_factor: f32,
+    shrink_load_factor: f32, // 0.0 if the table does not shrink automatically
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K: PartialEq + Hash, V, S: BuildHasher> Drop for SwissTable<K, V, S> {
+    fn drop(&mut self) {
+        self.drop_buckets();
+
+        dealloc_ctrl(self.ctrl, self.inner.mask + 1);
+        dealloc_slots::<K, V>(self.inner.buckets, self.inner.mask + 1);
+    }
+}
+
+impl<K, V, S> SwissTable<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher,
+{
+    pub fn new_with_properties(hasher: S, initial_size: usize, load_factor: f32) -> Self {
+        let size = initial_size.max(WIDTH);
+
+        Self {
+            hasher,
+            ctrl: alloc_ctrl(size),
+            inner: RawHashTable {
+                buckets: alloc_slots::<K, V>(size),
+                mask: size - 1,
+            },
+            count: 0,
+            tombstones: 0,
+            load_factor,
+            shrink_load_factor: 0.0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Shrink the table automatically when the load drops under `shrink_load_factor` by
+    /// removals. It is disabled by default(`0.0`), and `SHRINK_LOAD_FACTOR` is a reasonable value.
+    pub fn set_shrink_load_factor(&mut self, shrink_load_factor: f32) {
+        self.shrink_load_factor = shrink_load_factor;
+    }
+
+    fn hash_one<Q: ?Sized + Hash>(&self, key: &Q) -> u64 {
+        self.hasher.hash_one(key)
+    }
+
+    fn ctrl(&self, index: usize) -> u8 {
+        unsafe { *self.ctrl.as_ptr().add(index) }
+    }
+
+    // set the control byte, and its mirror if it is on the first group
+    fn set_ctrl(&mut self, index: usize, ctrl: u8) {
+        let mirror = (index.wrapping_sub(WIDTH) & self.inner.mask) + WIDTH;
+
+        unsafe {
+            *self.ctrl.as_ptr().add(index) = ctrl;
+            *self.ctrl.as_ptr().add(mirror) = ctrl;
+        }
+    }
+
+    fn group(&self, position: usize) -> Group {
+        unsafe { Group::load(self.ctrl.as_ptr().add(position)) }
+    }
+
+    fn slot(&self, index: usize) -> *mut Bucket<K, V> {
+        unsafe { (self.inner.buckets.as_ptr() as *mut Bucket<K, V>).add(index) }
+    }
+
+    fn is_full(&self, index: usize) -> bool {
+        self.ctrl(index) & 0x80 == 0
+    }
+
+    fn probe_seq(&self, hash: u64) -> ProbeSeq {
+        ProbeSeq {
+            position: hash as usize & self.inner.mask,
+            stride: 0,
+        }
+    }
+
+    fn find<Q>(&self, key: &Q, hash: u64) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + PartialEq,
+    {
+        let tag = h2(hash);
+        let mut probe = self.probe_seq(hash);
+
+        loop {
+            let group = self.group(probe.position);
+
+            for bit in group.match_byte(tag) {
+                let index = (probe.position + bit) & self.inner.mask;
+                let bucket = unsafe { &*self.slot(index) };
+
+                if bucket.hash == hash && bucket.key.borrow() == key {
+                    return Some(index);
+                }
+            }
+
+            // the key would be on the empty slot if it were inserted
+            if group.match_empty().any() {
+                return None;
+            }
+
+            probe.move_next(self.inner.mask);
+        }
+    }
+
+    // the first empty or deleted slot on the probe sequence, which always exists by the load factor
+    fn find_insert_slot(&self, hash: u64) -> usize {
+        let mut probe = self.probe_seq(hash);
+
+        loop {
+            if let Some(bit) = self.group(probe.position).match_empty_or_deleted().lowest() {
+                return (probe.position + bit) & self.inner.mask;
+            }
+
+            probe.move_next(self.inner.mask);
+        }
+    }
+
+    // insert the bucket known not to be on the table, and return the index where it is placed
+    fn insert_bucket(&mut self, bucket: Bucket<K, V>) -> usize {
+        let index = self.find_insert_slot(bucket.hash);
+
+        if self.ctrl(index) == DELETED {
+            self.tombstones -= 1;
+        }
+
+        self.set_ctrl(index, h2(bucket.hash));
+        unsafe { ptr::write(self.slot(index), bucket) };
+        self.count += 1;
+
+        index
+    }
+
+    fn take(&mut self, index: usize) -> Bucket<K, V> {
+        let empty_before = self
+            .group(index.wrapping_sub(WIDTH) & self.inner.mask)
+            .match_empty();
+        let empty_after = self.group(index).match_empty();
+
+        // if no group containing the slot has been full, no probe has passed it
+        if empty_before.leading_zeros() + empty_after.trailing_zeros() >= WIDTH {
+            self.set_ctrl(index, DELETED);
+            self.tombstones += 1;
+        } else {
+            self.set_ctrl(index, EMPTY);
+        }
+
+        self.count -= 1;
+        unsafe { ptr::read(self.slot(index)) }
+    }
+
+    fn drop_buckets(&mut self) {
+        for index in 0..=self.inner.mask {
+            if self.is_full(index) {
+                unsafe { ptr::drop_in_place(self.slot(index)) };
+            }
+        }
+    }
+
+    fn capacity_of(&self, size: usize) -> usize {
+        // an empty slot is left at least, which stops the probe
+        ((size as f32 * self.load_factor) as usize).min(size - 1)
+    }
+
+    fn reserve_one(&mut self) {
+        let size = self.inner.mask + 1;
+
+        if self.count + self.tombstones >= self.capacity_of(size) {
+            // the tombstones are cleaned up on the same size if they are the majority
+            if self.tombstones > self.count {
+                self.resize(size);
+            } else {
+                self.resize(size << 1);
+            }
+        }
+    }
+
+    // insert the bucket unless its key exists, and give the bucket back otherwise
+    fn insert_new(&mut self, bucket: Bucket<K, V>) -> Result<(), Bucket<K, V>> {
+        if self.find(&bucket.key, bucket.hash).is_some() {
+            return Err(bucket);
+        }
+
+        self.reserve_one();
+        self.insert_bucket(bucket);
+
+        Ok(())
+    }
+
+    // remove the bucket of the key, which the map and the set take the value or the key from
+    fn remove_bucket<Q>(&mut self, key: &Q) -> Result<Bucket<K, V>, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let index = self.find(key, self.hash_one(key)).ok_or(())?;
+        let bucket = self.take(index);
+
+        self.shrink_if_sparse();
+
+        Ok(bucket)
+    }
+
+    // shrink the table to the half load of the load factor if it gets sparse
+    fn shrink_if_sparse(&mut self) {
+        let size = self.inner.mask + 1;
+
+        if size > INITIAL_SIZE.max(WIDTH)
+            && (self.count as f32) < size as f32 * self.shrink_load_factor
+        {
+            self.resize(size_for(self.count * 2, self.load_factor));
+        }
+    }
+
+    fn resize(&mut self, new_size: usize) {
+        let new_size = new_size.max(WIDTH);
+        let old_size = self.inner.mask + 1;
+
+        let old_ctrl = mem::replace(&mut self.ctrl, alloc_ctrl(new_size));
+        let old_inner = mem::replace(
+            &mut self.inner,
+            RawHashTable {
+                buckets: alloc_slots::<K, V>(new_size),
+                mask: new_size - 1,
+            },
+        );
+
+        self.count = 0;
+        self.tombstones = 0;
+
+        let old_slot = old_inner.buckets.as_ptr() as *mut Bucket<K, V>;
+
+        for index in 0..old_size {
+            if unsafe { *old_ctrl.as_ptr().add(index) } & 0x80 == 0 {
+                self.insert_bucket(unsafe { ptr::read(old_slot.add(index)) });
+            }
+        }
+
+        dealloc_ctrl(old_ctrl, old_size);
+        dealloc_slots::<K, V>(old_inner.buckets, old_size);
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S> for SwissTable<K, V, S>
+where
+    K: PartialEq + Hash,
+    S: BuildHasher + Default,
+{
+    fn new() -> Self {
+        Self::new_with_properties(S::default(), INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    fn with_hasher(hasher: S) -> Self {
+        Self::new_with_properties(hasher, INITIAL_SIZE, LOAD_FACTOR)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::new_with_properties(S::default(), size_for(capacity, LOAD_FACTOR), LOAD_FACTOR)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Result<(), V> {
+        let hash = self.hash_one(&key);
+
+        self.insert_new(Bucket { key, hash, value })
+            .map_err(|bucket| bucket.value)
+    }
+
+    fn lookup<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let index = self.find(key, self.hash_one(key))?;
+
+        Some(unsafe { &(*self.slot(index)).value })
+    }
+
+    fn lookup_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        let index = self.find(key, self.hash_one(key))?;
+
+        Some(unsafe { &mut (*self.slot(index)).value })
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Result<V, ()>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + PartialEq,
+    {
+        self.remove_bucket(key).map(|bucket| bucket.value)
+    }
+
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity_of(self.inner.mask + 1)
+    }
+
+    fn tombstones(&self) -> usize {
+        self.tombstones
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        if self.count + self.tombstones + additional > self.capacity() {
+            let new_size = size_for(self.count + additional, self.load_factor);
+            self.resize(new_size.max(self.inner.mask + 1));
+        }
+    }
+
+    fn shrink_to(&mut self, min_capacity: usize) {
+        let new_size = size_for(self.count.max(min_capacity), self.load_factor).max(WIDTH);
+
+        if new_size < self.inner.mask + 1 {
+            self.resize(new_size);
+        }
+    }
+
+    fn insert_or_replace(&mut self, key: K, value: V) -> Option<V> {
+        self.entry(key).insert(value)
+    }
+
+    fn clear(&mut self) {
+        self.drop_buckets();
+
+        unsafe { ptr::write_bytes(self.ctrl.as_ptr(), EMPTY, self.inner.mask + 1 + WIDTH) };
+        self.count = 0;
+        self.tombstones = 0;
+    }
+
+    fn entry<'a>(
+        &'a mut self,
+        key: K,
+    ) -> Entry<impl OccupiedEntry<'a, Key = K, Value = V>, impl VacantEntry<'a, Key = K, Value = V>>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let hash = self.hash_one(&key);
+
+        if let Some(index) = self.find(&key, hash) {
+            Entry::Occupied(Occupied { table: self, index })
+        } else {
+            self.reserve_one();
+
+            Entry::Vacant(Vacant {
+                table: self,
+                key,
+                hash,
+            })
+        }
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a K, &'a V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        Iter::new(self.ctrl, &self.inner)
+    }
+
+    fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = (&'a K, &'a mut V)>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        IterMut::new(self.ctrl, &mut self.inner)
+    }
+}
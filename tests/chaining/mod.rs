@@ -0,0 +1,269 @@
+use all_of_hashtable::{
+    chaining::{
+        ChainingHashSet, ChainingHashTable, LinkedListChain, MoveToFrontChain, SmallVecChain,
+    },
+    HashMap, INITIAL_SIZE, LOAD_FACTOR, SHRINK_LOAD_FACTOR,
+};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+use crate::util::{
+    algebra_hashset, borrow_hashmap, crd_hashset, drop_hashmap, owned_hashmap, stress_hashmap,
+    Tracked, Unique,
+};
+
+#[test]
+fn test_crd_linked_list() {
+    let mut table = ChainingHashTable::<u64, u64, LinkedListChain<_, _>>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress_linked_list() {
+    let table = ChainingHashTable::<u64, u64, LinkedListChain<_, _>>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_crd_small_vec() {
+    let mut table = ChainingHashTable::<u64, u64, SmallVecChain<_, _>>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress_small_vec() {
+    let table = ChainingHashTable::<u64, u64, SmallVecChain<_, _>>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_crd_move_to_front() {
+    let mut table = ChainingHashTable::<u64, u64, MoveToFrontChain<_, _>>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_reorder_move_to_front() {
+    // a single chain, so the iteration follows the list
+    let mut table = ChainingHashTable::<u64, u64, MoveToFrontChain<_, _>>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        1,
+        100.0,
+    );
+
+    for i in 0..4 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    let keys = |table: &ChainingHashTable<u64, u64, MoveToFrontChain<_, _>>| {
+        table.iter().map(|(key, _)| *key).collect::<Vec<_>>()
+    };
+    assert_eq!(keys(&table), [3, 2, 1, 0]);
+
+    // the shared lookups keep the list, so both references stay valid
+    let (first, second) = (table.lookup(&0), table.lookup(&1));
+    assert_eq!((first, second), (Some(&0), Some(&1)));
+    assert_eq!(keys(&table), [3, 2, 1, 0]);
+
+    assert_eq!(table.lookup_mut(&0), Some(&mut 0));
+    assert_eq!(keys(&table), [0, 3, 2, 1]);
+}
+
+#[test]
+fn test_stress_move_to_front() {
+    let table = ChainingHashTable::<u64, u64, MoveToFrontChain<_, _>>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_drop_linked_list() {
+    drop_hashmap(ChainingHashTable::<Tracked, Tracked, LinkedListChain<_, _>>::new());
+}
+
+#[test]
+fn test_drop_move_to_front() {
+    drop_hashmap(ChainingHashTable::<Tracked, Tracked, MoveToFrontChain<_, _>>::new());
+}
+
+#[test]
+fn test_drop_small_vec() {
+    drop_hashmap(ChainingHashTable::<Tracked, Tracked, SmallVecChain<_, _>>::new());
+}
+
+#[test]
+fn test_borrow_linked_list() {
+    borrow_hashmap(ChainingHashTable::<String, u64, LinkedListChain<_, _>>::new());
+}
+
+#[test]
+fn test_borrow_move_to_front() {
+    borrow_hashmap(ChainingHashTable::<String, u64, MoveToFrontChain<_, _>>::new());
+}
+
+#[test]
+fn test_borrow_small_vec() {
+    borrow_hashmap(ChainingHashTable::<String, u64, SmallVecChain<_, _>>::new());
+}
+
+#[test]
+fn test_owned_linked_list() {
+    owned_hashmap(ChainingHashTable::<Unique, u64, LinkedListChain<_, _>>::new());
+}
+
+#[test]
+fn test_owned_small_vec() {
+    owned_hashmap(ChainingHashTable::<Unique, u64, SmallVecChain<_, _>>::new());
+}
+
+#[test]
+fn test_capacity_linked_list() {
+    let mut table = ChainingHashTable::<u64, u64, LinkedListChain<_, _>>::with_capacity(1000);
+    let capacity = table.capacity();
+    assert!(capacity >= 1000);
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), capacity);
+
+    table.reserve(1000);
+    assert!(table.capacity() >= 2000);
+
+    for i in 100..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    table.shrink_to_fit();
+    assert!(table.capacity() >= 100);
+    assert!(table.capacity() < 200);
+
+    for i in 0..100 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+}
+
+#[test]
+fn test_existing_key_on_load_limit() {
+    // the existing key neither grows the table by insert nor by entry
+    let mut table = ChainingHashTable::<u64, u64, MoveToFrontChain<_, _>>::new();
+    let capacity = table.capacity();
+
+    for i in 0..capacity as u64 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    assert_eq!(table.insert(0, 1), Err(1));
+    assert_eq!(table.entry(0).insert(1), Some(0));
+    assert_eq!(table.entry(1).or_insert(0), &mut 1);
+    assert_eq!(table.capacity(), capacity);
+
+    assert_eq!(table.insert(capacity as u64, 0), Ok(()));
+    assert!(table.capacity() > capacity);
+}
+
+#[test]
+fn test_auto_shrink_small_vec() {
+    let mut table = ChainingHashTable::<u64, u64, SmallVecChain<_, _>>::new();
+    table.set_shrink_load_factor(SHRINK_LOAD_FACTOR);
+
+    for i in 0..10_000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..10_000 {
+        assert_eq!(table.entry(i).remove(), Some(i));
+    }
+    assert_eq!(
+        table.capacity(),
+        (INITIAL_SIZE as f32 * LOAD_FACTOR) as usize
+    );
+}
+
+#[test]
+fn test_set_linked_list() {
+    crd_hashset::<ChainingHashSet<u64, LinkedListChain<_, _>>>();
+    algebra_hashset::<ChainingHashSet<u64, LinkedListChain<_, _>>>();
+}
+
+#[test]
+fn test_set_move_to_front() {
+    crd_hashset::<ChainingHashSet<u64, MoveToFrontChain<_, _>>>();
+    algebra_hashset::<ChainingHashSet<u64, MoveToFrontChain<_, _>>>();
+}
+
+#[test]
+fn test_set_small_vec() {
+    crd_hashset::<ChainingHashSet<u64, SmallVecChain<_, _>>>();
+    algebra_hashset::<ChainingHashSet<u64, SmallVecChain<_, _>>>();
+}
@@ -0,0 +1,215 @@
+use all_of_hashtable::{
+    cuckoo::{CuckooHashSet, CuckooHashTable, TABLES, WAYS},
+    HashMap, INITIAL_SIZE, LOAD_FACTOR, SHRINK_LOAD_FACTOR,
+};
+use std::{
+    collections::hash_map::{DefaultHasher, RandomState},
+    hash::BuildHasherDefault,
+};
+
+use crate::util::{
+    algebra_hashset, borrow_hashmap, crd_hashset, drop_hashmap, owned_hashmap, stress_hashmap,
+    Tracked, Unique,
+};
+
+#[test]
+fn test_crd() {
+    let mut table = CuckooHashTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        RandomState::new(),
+        TABLES,
+        WAYS,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress() {
+    let table = CuckooHashTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        RandomState::new(),
+        TABLES,
+        WAYS,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_stress_classic() {
+    // two tables of a slot, which cycles often over the half load
+    let table = CuckooHashTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        RandomState::new(),
+        2,
+        1,
+        INITIAL_SIZE,
+        0.45,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_stress_d_ary() {
+    let table = CuckooHashTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        RandomState::new(),
+        4,
+        1,
+        INITIAL_SIZE,
+        0.9,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_rehash_on_cycle() {
+    // the load over the threshold of the classic cuckoo hashing makes the cycles, but the
+    // rehash by the new seeds and the growth keep every key
+    let mut table = CuckooHashTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        RandomState::new(),
+        2,
+        1,
+        1024,
+        0.95,
+    );
+
+    for i in 0..1945 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1945 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+    assert_eq!(table.len(), 1945);
+    assert_eq!(table.iter().count(), 1945);
+}
+
+#[test]
+fn test_entry_on_cycle() {
+    // the vacant entry gives the value where the displacements and the rehashes leave it
+    let mut table = CuckooHashTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        RandomState::new(),
+        2,
+        1,
+        1024,
+        0.95,
+    );
+
+    for i in 0..1945 {
+        let value = table.entry(i).or_insert(i);
+        assert_eq!(*value, i);
+        *value += 1;
+    }
+
+    for i in 0..1945 {
+        assert_eq!(table.lookup(&i), Some(&(i + 1)));
+    }
+}
+
+#[test]
+fn test_iter() {
+    let mut table = CuckooHashTable::<u64, u64>::new();
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for (_, value) in table.iter_mut() {
+        *value *= 2;
+    }
+
+    let mut entries = table.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+    entries.sort_unstable();
+    assert_eq!(entries, (0..1000).map(|i| (i, i * 2)).collect::<Vec<_>>());
+
+    let mut entries = table.into_iter().collect::<Vec<_>>();
+    entries.sort_unstable();
+    assert_eq!(entries, (0..1000).map(|i| (i, i * 2)).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_capacity() {
+    let mut table = CuckooHashTable::<u64, u64>::with_capacity(1000);
+    let capacity = table.capacity();
+    assert!(capacity >= 1000);
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert!(table.capacity() >= capacity);
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    table.shrink_to_fit();
+    assert_eq!(
+        table.capacity(),
+        ((TABLES * WAYS * INITIAL_SIZE) as f32 * LOAD_FACTOR) as usize
+    );
+}
+
+#[test]
+fn test_auto_shrink() {
+    let mut table = CuckooHashTable::<u64, u64>::new();
+    table.set_shrink_load_factor(SHRINK_LOAD_FACTOR);
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    let capacity = table.capacity();
+
+    for i in 0..990 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+    assert!(table.capacity() < capacity);
+
+    for i in 990..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+}
+
+#[test]
+fn test_drop() {
+    drop_hashmap(CuckooHashTable::<Tracked, Tracked>::new());
+}
+
+#[test]
+fn test_borrow() {
+    borrow_hashmap(CuckooHashTable::<String, u64>::new());
+}
+
+#[test]
+fn test_owned() {
+    owned_hashmap(CuckooHashTable::<Unique, u64>::new());
+}
+
+#[test]
+fn test_set() {
+    crd_hashset::<CuckooHashSet<u64>>();
+    algebra_hashset::<CuckooHashSet<u64>>();
+}
@@ -0,0 +1,174 @@
+use all_of_hashtable::{
+    hopscotch::{HopscotchHashSet, HopscotchHashTable, NEIGHBORHOOD},
+    HashMap, INITIAL_SIZE, LOAD_FACTOR, SHRINK_LOAD_FACTOR,
+};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+use crate::util::{
+    algebra_hashset, borrow_hashmap, crd_hashset, drop_hashmap, owned_hashmap, stress_hashmap,
+    Tracked, Unique,
+};
+
+#[test]
+fn test_crd() {
+    let mut table = HopscotchHashTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        NEIGHBORHOOD,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress() {
+    let table = HopscotchHashTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        NEIGHBORHOOD,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_stress_small_neighborhood() {
+    // the small neighborhood hops the buckets often, and resizes when it cannot
+    for neighborhood in [1, 2, 4] {
+        let table = HopscotchHashTable::<u64, u64>::new_with_properties(
+            BuildHasherDefault::<DefaultHasher>::default(),
+            neighborhood,
+            INITIAL_SIZE,
+            0.9,
+        );
+
+        stress_hashmap(table, 100_000);
+    }
+}
+
+#[test]
+fn test_high_load() {
+    let mut table = HopscotchHashTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        64,
+        1024,
+        0.95,
+    );
+
+    for i in 0..972 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..972 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+    assert_eq!(table.len(), 972);
+}
+
+#[test]
+#[should_panic]
+fn test_neighborhood_over_bitmap() {
+    HopscotchHashTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        65,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+}
+
+#[test]
+fn test_iter() {
+    let mut table = HopscotchHashTable::<u64, u64>::new();
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for (_, value) in table.iter_mut() {
+        *value *= 2;
+    }
+
+    let mut entries = table.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+    entries.sort_unstable();
+    assert_eq!(entries, (0..1000).map(|i| (i, i * 2)).collect::<Vec<_>>());
+
+    let mut entries = table.into_iter().collect::<Vec<_>>();
+    entries.sort_unstable();
+    assert_eq!(entries, (0..1000).map(|i| (i, i * 2)).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_capacity() {
+    let mut table = HopscotchHashTable::<u64, u64>::with_capacity(1000);
+    let capacity = table.capacity();
+    assert!(capacity >= 1000);
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), capacity);
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    table.shrink_to_fit();
+    assert_eq!(table.capacity(), (INITIAL_SIZE as f32 * LOAD_FACTOR) as usize);
+}
+
+#[test]
+fn test_auto_shrink() {
+    let mut table = HopscotchHashTable::<u64, u64>::new();
+    table.set_shrink_load_factor(SHRINK_LOAD_FACTOR);
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    let capacity = table.capacity();
+
+    for i in 0..990 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+    assert!(table.capacity() < capacity);
+
+    for i in 990..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+}
+
+#[test]
+fn test_drop() {
+    drop_hashmap(HopscotchHashTable::<Tracked, Tracked>::new());
+}
+
+#[test]
+fn test_borrow() {
+    borrow_hashmap(HopscotchHashTable::<String, u64>::new());
+}
+
+#[test]
+fn test_owned() {
+    owned_hashmap(HopscotchHashTable::<Unique, u64>::new());
+}
+
+#[test]
+fn test_set() {
+    crd_hashset::<HopscotchHashSet<u64>>();
+    algebra_hashset::<HopscotchHashSet<u64>>();
+}
@@ -0,0 +1,134 @@
+use all_of_hashtable::{lock_free::LockFreeHashMap, ConcurrentHashMap};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault, thread};
+
+use crate::util::{stress_concurrent, Unique};
+
+const THREADS: u64 = 8;
+
+#[test]
+fn test_crd() {
+    let map = LockFreeHashMap::<u64, u64>::new();
+
+    for i in 0..1000 {
+        assert_eq!(map.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(map.lookup_cloned(&i), Some(i));
+        assert_eq!(map.insert(i, i), Err(i));
+    }
+    assert_eq!(map.len(), 1000);
+
+    for i in 0..1000 {
+        assert_eq!(map.insert_or_replace(i, i + 1), Some(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(map.remove(&i), Ok(i + 1));
+        assert_eq!(map.remove(&i), Err(()));
+    }
+
+    for i in 0..1000 {
+        assert!(map.lookup(&i).is_none());
+    }
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_clear() {
+    let map = LockFreeHashMap::<u64, String>::new();
+
+    for i in 0..100 {
+        assert_eq!(map.insert(i, i.to_string()), Ok(()));
+    }
+
+    map.clear();
+    assert!(map.is_empty());
+
+    for i in 0..100 {
+        assert!(map.lookup(&i).is_none());
+        assert_eq!(map.insert(i, i.to_string()), Ok(()));
+    }
+    assert_eq!(map.len(), 100);
+}
+
+#[test]
+fn test_concurrent_migration() {
+    // all threads insert on the smallest table, so they run into the migrations together
+    let map = LockFreeHashMap::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        2,
+        0.7,
+    );
+
+    thread::scope(|scope| {
+        for t in 0..THREADS {
+            let map = &map;
+
+            scope.spawn(move || {
+                for i in 0..10_000 {
+                    let key = i * THREADS + t;
+
+                    assert_eq!(map.insert(key, key), Ok(()));
+                    assert_eq!(map.lookup_cloned(&key), Some(key));
+                }
+            });
+        }
+    });
+
+    assert_eq!(map.len() as u64, THREADS * 10_000);
+
+    for key in 0..THREADS * 10_000 {
+        assert_eq!(map.lookup_cloned(&key), Some(key));
+    }
+}
+
+#[test]
+fn test_owned() {
+    // the key is moved into the table, so it need not be cloned
+    let map = LockFreeHashMap::<Unique, u64>::new();
+
+    for i in 0..1000 {
+        assert_eq!(map.insert(Unique(i), i), Ok(()));
+    }
+
+    assert_eq!(map.insert(Unique(0), 1), Err(1));
+    assert_eq!(map.insert_or_replace(Unique(0), 1), Some(0));
+    assert_eq!(map.lookup_cloned(&Unique(0)), Some(1));
+    assert_eq!(map.len(), 1000);
+}
+
+#[test]
+fn test_stress_concurrent() {
+    stress_concurrent(LockFreeHashMap::<u64, u64>::new(), 100_000);
+}
+
+#[test]
+fn test_shared_keys() {
+    // the replaced values on the shared keys are handed over to exactly one thread
+    let map = LockFreeHashMap::<u64, u64>::new();
+
+    let total: u64 = thread::scope(|scope| {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let map = &map;
+
+                scope.spawn(move || {
+                    let mut sum = 0;
+
+                    for i in 0..10_000 {
+                        sum += map.insert_or_replace(i % 16, 1).unwrap_or(0);
+                    }
+
+                    sum
+                })
+            })
+            .collect();
+
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    });
+
+    // every inserted 1 is replaced once, except the last ones on the keys
+    assert_eq!(map.len(), 16);
+    assert_eq!(total + 16, THREADS * 10_000);
+}
@@ -0,0 +1,197 @@
+use all_of_hashtable::{lock_free::LockFreeHashMap, ConcurrentHashMap};
+use rand::{thread_rng, Rng};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::BuildHasherDefault,
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+};
+
+const THREADS: u64 = 4;
+const OPS: u64 = 24;
+const KEYS: u64 = 4;
+const ROUNDS: usize = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Insert(u64),
+    Lookup,
+    Remove,
+    Replace(u64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Ret {
+    Inserted(bool),
+    Value(Option<u64>),
+}
+
+#[derive(Debug)]
+struct Event {
+    key: u64,
+    op: Op,
+    ret: Ret,
+    call: u64,    // the time when the operation is called
+    return_: u64, // the time when the operation returns
+}
+
+// the sequential specification
+fn apply(map: &mut HashMap<u64, u64>, key: u64, op: Op) -> Ret {
+    match op {
+        Op::Insert(value) => {
+            let inserted = !map.contains_key(&key);
+            map.entry(key).or_insert(value);
+
+            Ret::Inserted(inserted)
+        }
+        Op::Lookup => Ret::Value(map.get(&key).cloned()),
+        Op::Remove => Ret::Value(map.remove(&key)),
+        Op::Replace(value) => Ret::Value(map.insert(key, value)),
+    }
+}
+
+// find the order of the events, which respects their real time and the specification
+fn search(
+    events: &[&Event],
+    done: u128,
+    map: &HashMap<u64, u64>,
+    visited: &mut HashSet<(u128, Vec<(u64, u64)>)>,
+) -> bool {
+    if done.count_ones() as usize == events.len() {
+        return true;
+    }
+
+    let state = (done, map.iter().map(|(k, v)| (*k, *v)).collect());
+
+    if !visited.insert(state) {
+        return false;
+    }
+
+    let pending = || (0..events.len()).filter(|i| done & (1 << i) == 0);
+
+    // an event can be the next only if it is called before all pending events return
+    let first_return = pending().map(|i| events[i].return_).min().unwrap();
+
+    pending()
+        .filter(|&i| events[i].call < first_return)
+        .any(|i| {
+            let mut next = map.clone();
+
+            apply(&mut next, events[i].key, events[i].op) == events[i].ret
+                && search(events, done | (1 << i), &next, visited)
+        })
+}
+
+// the linearizability is local, so the history is checked for each key
+fn linearizable(history: &[Event]) -> bool {
+    let mut keys: HashMap<u64, Vec<&Event>> = HashMap::new();
+
+    for event in history {
+        keys.entry(event.key).or_default().push(event);
+    }
+
+    keys.values().all(|events| {
+        assert!(events.len() <= 128);
+        search(events, 0, &HashMap::new(), &mut HashSet::new())
+    })
+}
+
+fn record(map: &LockFreeHashMap<u64, u64>, clock: &AtomicU64, t: u64) -> Vec<Event> {
+    let mut rng = thread_rng();
+
+    (0..OPS)
+        .map(|i| {
+            let key = rng.gen_range(0..KEYS);
+            // the values are unique to tell the operations apart
+            let value = t << 32 | i;
+
+            let op = match rng.gen_range(0..4) {
+                0 => Op::Insert(value),
+                1 => Op::Lookup,
+                2 => Op::Remove,
+                _ => Op::Replace(value),
+            };
+
+            let call = clock.fetch_add(1, Ordering::SeqCst);
+            let ret = match op {
+                Op::Insert(value) => Ret::Inserted(map.insert(key, value).is_ok()),
+                Op::Lookup => Ret::Value(map.lookup_cloned(&key)),
+                Op::Remove => Ret::Value(map.remove(&key).ok()),
+                Op::Replace(value) => Ret::Value(map.insert_or_replace(key, value)),
+            };
+            let return_ = clock.fetch_add(1, Ordering::SeqCst);
+
+            Event {
+                key,
+                op,
+                ret,
+                call,
+                return_,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_checker() {
+    let event = |op, ret, call, return_| Event {
+        key: 0,
+        op,
+        ret,
+        call,
+        return_,
+    };
+
+    // the lookup overlapping the insert may see it or not
+    for seen in [None, Some(1)] {
+        let history = [
+            event(Op::Insert(1), Ret::Inserted(true), 0, 2),
+            event(Op::Lookup, Ret::Value(seen), 1, 3),
+        ];
+        assert!(linearizable(&history));
+    }
+
+    // but the lookup after the insert should see it
+    let history = [
+        event(Op::Insert(1), Ret::Inserted(true), 0, 1),
+        event(Op::Lookup, Ret::Value(None), 2, 3),
+    ];
+    assert!(!linearizable(&history));
+
+    // the two removes cannot take the same value
+    let history = [
+        event(Op::Insert(1), Ret::Inserted(true), 0, 1),
+        event(Op::Remove, Ret::Value(Some(1)), 2, 4),
+        event(Op::Remove, Ret::Value(Some(1)), 3, 5),
+    ];
+    assert!(!linearizable(&history));
+}
+
+#[test]
+fn test_linearizability() {
+    for _ in 0..ROUNDS {
+        // the smallest table is migrated during the operations
+        let map = LockFreeHashMap::<u64, u64>::new_with_properties(
+            BuildHasherDefault::<DefaultHasher>::default(),
+            2,
+            0.7,
+        );
+        let clock = AtomicU64::new(0);
+
+        let history: Vec<Event> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..THREADS)
+                .map(|t| {
+                    let (map, clock) = (&map, &clock);
+                    scope.spawn(move || record(map, clock, t))
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+
+        assert!(linearizable(&history), "{:#?}", history);
+    }
+}
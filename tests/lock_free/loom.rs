@@ -0,0 +1,115 @@
+use all_of_hashtable::{lock_free::LockFreeHashMap, ConcurrentHashMap};
+use loom::{model::Builder, sync::Arc, thread};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+type Map = LockFreeHashMap<u64, u64>;
+
+// the table of 2 slots holds only 1 key, so the second key migrates it
+fn small_map() -> Arc<Map> {
+    Arc::new(Map::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        2,
+        0.7,
+    ))
+}
+
+fn model<F: Fn() + Sync + Send + 'static>(f: F) {
+    let mut builder = Builder::new();
+    builder.preemption_bound = Some(2);
+    builder.check(f);
+}
+
+#[test]
+fn loom_insert_migration() {
+    model(|| {
+        let map = small_map();
+
+        let handles: Vec<_> = (0..2)
+            .map(|key| {
+                let map = map.clone();
+                thread::spawn(move || assert_eq!(map.insert(key, key), Ok(())))
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.lookup_cloned(&0), Some(0));
+        assert_eq!(map.lookup_cloned(&1), Some(1));
+    });
+}
+
+#[test]
+fn loom_insert_same_key() {
+    model(|| {
+        let map = small_map();
+
+        let handles: Vec<_> = (0..2)
+            .map(|value| {
+                let map = map.clone();
+                thread::spawn(move || map.insert(0, value).is_ok())
+            })
+            .collect();
+
+        let inserted: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        // only one of them wins, and its value stays
+        assert_eq!(inserted.iter().filter(|&&ok| ok).count(), 1);
+        let winner = inserted.iter().position(|&ok| ok).unwrap() as u64;
+        assert_eq!(map.lookup_cloned(&0), Some(winner));
+    });
+}
+
+#[test]
+fn loom_remove_during_migration() {
+    model(|| {
+        let map = small_map();
+        assert_eq!(map.insert(0, 0), Ok(()));
+
+        let remover = {
+            let map = map.clone();
+            thread::spawn(move || map.remove(&0))
+        };
+
+        let inserter = {
+            let map = map.clone();
+            thread::spawn(move || map.insert(1, 1))
+        };
+
+        // the value is removed once, whether it is migrated or not
+        assert_eq!(remover.join().unwrap(), Ok(0));
+        assert_eq!(inserter.join().unwrap(), Ok(()));
+
+        assert_eq!(map.lookup_cloned(&0), None);
+        assert_eq!(map.lookup_cloned(&1), Some(1));
+        assert_eq!(map.len(), 1);
+    });
+}
+
+#[test]
+fn loom_replace_and_lookup() {
+    model(|| {
+        let map = small_map();
+        assert_eq!(map.insert(0, 0), Ok(()));
+
+        let replacer = {
+            let map = map.clone();
+            thread::spawn(move || map.insert_or_replace(0, 1))
+        };
+
+        let inserter = {
+            let map = map.clone();
+            thread::spawn(move || map.insert(1, 1))
+        };
+
+        // the lookup sees the old or the new value, never nothing
+        let seen = map.lookup_cloned(&0);
+        assert!(seen == Some(0) || seen == Some(1));
+
+        assert_eq!(replacer.join().unwrap(), Some(0));
+        assert_eq!(inserter.join().unwrap(), Ok(()));
+        assert_eq!(map.lookup_cloned(&0), Some(1));
+    });
+}
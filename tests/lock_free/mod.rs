@@ -0,0 +1,6 @@
+#[cfg(not(loom))]
+mod concurrent;
+#[cfg(not(loom))]
+mod linearizability;
+#[cfg(loom)]
+mod loom;
@@ -0,0 +1,133 @@
+use all_of_hashtable::{
+    metrics::Metrics,
+    open_addressing::{
+        Bucket, FcfsLinearProbing, LcfsLinearProbing, OpenAddressingHashTable,
+        RobinHoodLinearProbing,
+    },
+    EntryStrategy, HashMap, INITIAL_SIZE, LOAD_FACTOR,
+};
+use std::{hash::BuildHasherDefault, mem};
+
+use crate::util::IdentityHasher;
+
+fn identity_table<E>(
+    entry: E,
+) -> OpenAddressingHashTable<u64, u64, E, BuildHasherDefault<IdentityHasher>>
+where
+    E: EntryStrategy<u64, Bucket<u64, u64>>,
+{
+    OpenAddressingHashTable::new_with_properties(
+        BuildHasherDefault::<IdentityHasher>::default(),
+        entry,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    )
+}
+
+#[test]
+fn test_metrics_reset() {
+    let mut table = identity_table(FcfsLinearProbing::default());
+
+    Metrics::reset();
+    assert_eq!(table.insert(0, 0), Ok(()));
+    assert_ne!(Metrics::snapshot(), Metrics::default());
+
+    Metrics::reset();
+    assert_eq!(Metrics::snapshot(), Metrics::default());
+}
+
+#[test]
+fn test_metrics_lookup() {
+    let mut table = identity_table(FcfsLinearProbing::default());
+
+    // the key 8 collides with 0, and is placed after the cluster of 0..3
+    for key in [0, 1, 2, 8] {
+        assert_eq!(table.insert(key, key), Ok(()));
+    }
+
+    Metrics::reset();
+    assert_eq!(table.lookup(&8), Some(&8));
+    assert_eq!(
+        Metrics::snapshot(),
+        Metrics {
+            probes: 4,
+            comparisons: 4,
+            hashes: 1,
+            key_eq_checks: 1,
+            ..Metrics::default()
+        }
+    );
+
+    // the absent key walks the cluster until None
+    Metrics::reset();
+    assert_eq!(table.lookup(&16), None);
+    assert_eq!(
+        Metrics::snapshot(),
+        Metrics {
+            probes: 5,
+            comparisons: 4,
+            hashes: 1,
+            ..Metrics::default()
+        }
+    );
+}
+
+#[test]
+fn test_metrics_robin_hood() {
+    let mut table = identity_table(RobinHoodLinearProbing);
+
+    // the key 8 is placed on 1 closer to its home than 1, which is pushed back
+    for key in [0, 1, 8] {
+        assert_eq!(table.insert(key, key), Ok(()));
+    }
+
+    Metrics::reset();
+    assert_eq!(table.lookup(&1), Some(&1));
+    assert_eq!(
+        Metrics::snapshot(),
+        Metrics {
+            probes: 2,
+            comparisons: 2,
+            hashes: 1,
+            key_eq_checks: 1,
+            ..Metrics::default()
+        }
+    );
+}
+
+#[test]
+fn test_metrics_displacements() {
+    let mut table = identity_table(LcfsLinearProbing::default());
+
+    for key in [0, 1, 2] {
+        assert_eq!(table.insert(key, key), Ok(()));
+    }
+
+    // the key 8 takes its home 0, and evicts 0 to the first None after 1 and 2
+    Metrics::reset();
+    assert_eq!(table.insert(8, 8), Ok(()));
+    assert_eq!(Metrics::snapshot().displacements, 1);
+
+    for key in [0, 1, 2, 8] {
+        assert_eq!(table.lookup(&key), Some(&key));
+    }
+}
+
+#[test]
+fn test_metrics_resize() {
+    let mut table = identity_table(FcfsLinearProbing::default());
+
+    for key in 0..4 {
+        assert_eq!(table.insert(key, key), Ok(()));
+    }
+
+    Metrics::reset();
+    table.reserve(INITIAL_SIZE);
+
+    let metrics = Metrics::snapshot();
+    assert_eq!(metrics.resizes, 1);
+    assert_eq!(
+        metrics.bytes_moved,
+        4 * mem::size_of::<Bucket<u64, u64>>() as u64
+    );
+}
@@ -0,0 +1,64 @@
+use all_of_hashtable::{
+    open_addressing::{
+        FcfsDoubleHashing, FcfsLinearProbing, FcfsQuadraticProbing, FcfsRandomProbing,
+        LcfsDoubleHashing, LcfsLinearProbing, LcfsRandomProbing, OpenAddressingHashTable,
+        RobinHoodLinearProbing,
+    },
+    HashMap, INITIAL_SIZE, LOAD_FACTOR,
+};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+use crate::util::borrow_hashmap;
+
+#[test]
+fn test_borrow_fcfs_linear_probing() {
+    borrow_hashmap(OpenAddressingHashTable::<String, u64, FcfsLinearProbing>::new());
+}
+
+#[test]
+fn test_borrow_fcfs_backshift_linear_probing() {
+    borrow_hashmap(
+        OpenAddressingHashTable::<String, u64, _>::new_with_properties(
+            BuildHasherDefault::<DefaultHasher>::default(),
+            FcfsLinearProbing::new(false),
+            INITIAL_SIZE,
+            LOAD_FACTOR,
+        ),
+    );
+}
+
+#[test]
+fn test_borrow_lcfs_linear_probing() {
+    borrow_hashmap(OpenAddressingHashTable::<String, u64, LcfsLinearProbing>::new());
+}
+
+#[test]
+fn test_borrow_robin_hood_linear_probing() {
+    borrow_hashmap(OpenAddressingHashTable::<String, u64, RobinHoodLinearProbing>::new());
+}
+
+#[test]
+fn test_borrow_fcfs_quadratic_probing() {
+    borrow_hashmap(OpenAddressingHashTable::<String, u64, FcfsQuadraticProbing>::new());
+}
+
+// the probe sequences of double hashing and random probing are drawn from the borrowed key
+#[test]
+fn test_borrow_fcfs_double_hashing() {
+    borrow_hashmap(OpenAddressingHashTable::<String, u64, FcfsDoubleHashing>::new());
+}
+
+#[test]
+fn test_borrow_lcfs_double_hashing() {
+    borrow_hashmap(OpenAddressingHashTable::<String, u64, LcfsDoubleHashing>::new());
+}
+
+#[test]
+fn test_borrow_fcfs_random_probing() {
+    borrow_hashmap(OpenAddressingHashTable::<String, u64, FcfsRandomProbing>::new());
+}
+
+#[test]
+fn test_borrow_lcfs_random_probing() {
+    borrow_hashmap(OpenAddressingHashTable::<String, u64, LcfsRandomProbing>::new());
+}
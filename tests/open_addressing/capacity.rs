@@ -0,0 +1,156 @@
+use all_of_hashtable::{
+    open_addressing::{FcfsLinearProbing, OpenAddressingHashTable, RobinHoodLinearProbing},
+    HashMap, INITIAL_SIZE, LOAD_FACTOR, SHRINK_LOAD_FACTOR,
+};
+
+#[test]
+fn test_with_capacity() {
+    let mut table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::with_capacity(1000);
+    let capacity = table.capacity();
+    assert!(capacity >= 1000);
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), capacity);
+
+    let table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::with_capacity(0);
+    assert_eq!(
+        table.capacity(),
+        (INITIAL_SIZE as f32 * LOAD_FACTOR) as usize
+    );
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn test_with_capacity_overflow() {
+    OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::with_capacity(usize::MAX);
+}
+
+#[test]
+fn test_reserve() {
+    let mut table = OpenAddressingHashTable::<u64, u64, RobinHoodLinearProbing>::new();
+
+    for i in 0..100 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    table.reserve(10_000);
+    let capacity = table.capacity();
+    assert!(capacity >= 10_100);
+
+    for i in 100..10_100 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), capacity);
+
+    // enough room already
+    table.reserve(1);
+    assert_eq!(table.capacity(), capacity);
+}
+
+#[test]
+fn test_existing_key_at_load_limit() {
+    let mut table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::new();
+    let capacity = table.capacity();
+
+    for i in 0..capacity as u64 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    // the key is found before making room, so the full table stays as it is
+    assert_eq!(table.insert(0, 1), Err(1));
+    assert_eq!(table.insert_or_replace(0, 1), Some(0));
+    assert_eq!(table.entry(0).or_insert(2), &mut 1);
+    assert_eq!(table.capacity(), capacity);
+}
+
+#[test]
+fn test_reserve_with_tombstones() {
+    let mut table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::new();
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..900 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    // the tombstones are cleared by the resize, so the room is for the live entries
+    let capacity = table.capacity();
+    table.reserve(capacity - 100);
+    assert_eq!(table.tombstones(), 0);
+    assert_eq!(table.capacity(), capacity);
+
+    for i in 1000..capacity as u64 + 900 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), capacity);
+}
+
+#[test]
+fn test_shrink() {
+    let mut table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::new();
+
+    for i in 0..10_000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 100..10_000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    let capacity = table.capacity();
+
+    table.shrink_to(1000);
+    assert!(table.capacity() >= 1000);
+    assert!(table.capacity() < capacity);
+    assert_eq!(table.tombstones(), 0);
+
+    table.shrink_to_fit();
+    assert!(table.capacity() >= 100);
+    assert!(table.capacity() < 200);
+
+    // shrink_to never grows
+    let capacity = table.capacity();
+    table.shrink_to(10_000);
+    assert_eq!(table.capacity(), capacity);
+
+    for i in 0..100 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    table.clear();
+    table.shrink_to_fit();
+    assert_eq!(
+        table.capacity(),
+        (INITIAL_SIZE as f32 * LOAD_FACTOR) as usize
+    );
+}
+
+#[test]
+fn test_auto_shrink() {
+    let mut table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::new();
+    table.set_shrink_load_factor(SHRINK_LOAD_FACTOR);
+
+    for i in 0..10_000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    let capacity = table.capacity();
+
+    for i in 0..9_900 {
+        assert_eq!(table.remove(&i), Ok(i));
+        assert!(table.len() as f32 >= table.capacity() as f32 * SHRINK_LOAD_FACTOR / LOAD_FACTOR);
+    }
+    assert!(table.capacity() < capacity / 10);
+
+    for i in 9_900..10_000 {
+        assert_eq!(table.entry(i).remove(), Some(i));
+    }
+    assert_eq!(
+        table.capacity(),
+        (INITIAL_SIZE as f32 * LOAD_FACTOR) as usize
+    );
+}
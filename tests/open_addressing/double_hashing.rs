@@ -1,8 +1,11 @@
 use all_of_hashtable::{
-    open_addressing::{FcfsDoubleHashing, OpenAddressingHashTable},
+    open_addressing::{FcfsDoubleHashing, LcfsDoubleHashing, OpenAddressingHashTable},
     HashMap, INITIAL_SIZE, LOAD_FACTOR,
 };
-use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+use std::{
+    collections::hash_map::{DefaultHasher, RandomState},
+    hash::{BuildHasherDefault, Hasher},
+};
 
 use crate::util::stress_hashmap;
 
@@ -10,13 +13,13 @@ use crate::util::stress_hashmap;
 fn test_crd_fcfs() {
     let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
         BuildHasherDefault::<DefaultHasher>::default(),
-        FcfsDoubleHashing::default(),
+        FcfsDoubleHashing::new(),
         INITIAL_SIZE,
         LOAD_FACTOR,
     );
 
     for i in 0..1000 {
-        assert_eq!(table.insert(&i, i), Ok(()));
+        assert_eq!(table.insert(i, i), Ok(()));
     }
 
     for i in 0..1000 {
@@ -36,10 +39,141 @@ fn test_crd_fcfs() {
 fn test_stress_fcfs() {
     let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
         BuildHasherDefault::<DefaultHasher>::default(),
-        FcfsDoubleHashing::default(),
+        FcfsDoubleHashing::new(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_crd_lcfs() {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsDoubleHashing::new(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress_lcfs() {
+    let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsDoubleHashing::new(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+// the adversarial secondary hasher, whose hash is always a multiple of any table size
+#[derive(Default)]
+struct EvenHasher(u64);
+
+impl Hasher for EvenHasher {
+    fn finish(&self) -> u64 {
+        self.0 << 32
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 << 8) | *byte as u64;
+        }
+    }
+}
+
+#[test]
+fn test_fill_without_resize() {
+    // the full load does not resize if the step visits every bucket
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsDoubleHashing::with_hasher(RandomState::new()),
+        1024,
+        1.0,
+    );
+
+    for i in 0..1024 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), 1024);
+
+    for i in 0..1024 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+}
+
+#[test]
+fn test_adversarial_hasher() {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsDoubleHashing::<BuildHasherDefault<EvenHasher>>::default(),
+        1024,
+        1.0,
+    );
+
+    for i in 0..1024 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), 1024);
+
+    for i in 0..1024 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+    assert!(table.is_empty());
+}
+
+#[test]
+fn test_stress_fcfs_adversarial_hasher() {
+    let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsDoubleHashing::<BuildHasherDefault<EvenHasher>>::with_hasher(Default::default()),
         INITIAL_SIZE,
         LOAD_FACTOR,
     );
 
     stress_hashmap(table, 100_000);
 }
+
+#[test]
+fn test_fill_without_resize_lcfs() {
+    // the evicted buckets follow their own probe sequences until the last empty bucket
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsDoubleHashing::new(),
+        1024,
+        1.0,
+    );
+
+    for i in 0..1024 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), 1024);
+
+    for i in 0..1024 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1024 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+    assert!(table.is_empty());
+}
@@ -0,0 +1,98 @@
+use all_of_hashtable::{
+    open_addressing::{
+        FcfsDoubleHashing, FcfsLinearProbing, FcfsQuadraticProbing, FcfsRandomProbing,
+        LcfsDoubleHashing, LcfsLinearProbing, LcfsQuadraticProbing, LcfsRandomProbing,
+        OpenAddressingHashTable, RobinHoodLinearProbing,
+    },
+    HashMap, INITIAL_SIZE, LOAD_FACTOR,
+};
+use std::{cell::Cell, collections::hash_map::DefaultHasher, hash::BuildHasherDefault, rc::Rc};
+
+use crate::util::{drop_hashmap, Tracked};
+
+#[test]
+fn test_drop_fcfs_linear_probing() {
+    drop_hashmap(OpenAddressingHashTable::<Tracked, Tracked, FcfsLinearProbing>::new());
+}
+
+#[test]
+fn test_drop_fcfs_backshift_linear_probing() {
+    drop_hashmap(
+        OpenAddressingHashTable::<Tracked, Tracked, _>::new_with_properties(
+            BuildHasherDefault::<DefaultHasher>::default(),
+            FcfsLinearProbing::new(false),
+            INITIAL_SIZE,
+            LOAD_FACTOR,
+        ),
+    );
+}
+
+#[test]
+fn test_drop_lcfs_linear_probing() {
+    drop_hashmap(OpenAddressingHashTable::<Tracked, Tracked, LcfsLinearProbing>::new());
+}
+
+#[test]
+fn test_drop_robin_hood_linear_probing() {
+    drop_hashmap(OpenAddressingHashTable::<
+        Tracked,
+        Tracked,
+        RobinHoodLinearProbing,
+    >::new());
+}
+
+#[test]
+fn test_drop_fcfs_quadratic_probing() {
+    drop_hashmap(OpenAddressingHashTable::<
+        Tracked,
+        Tracked,
+        FcfsQuadraticProbing,
+    >::new());
+}
+
+#[test]
+fn test_drop_fcfs_double_hashing() {
+    drop_hashmap(OpenAddressingHashTable::<Tracked, Tracked, FcfsDoubleHashing>::new());
+}
+
+#[test]
+fn test_drop_lcfs_quadratic_probing() {
+    drop_hashmap(OpenAddressingHashTable::<
+        Tracked,
+        Tracked,
+        LcfsQuadraticProbing,
+    >::new());
+}
+
+#[test]
+fn test_drop_lcfs_double_hashing() {
+    drop_hashmap(OpenAddressingHashTable::<Tracked, Tracked, LcfsDoubleHashing>::new());
+}
+
+#[test]
+fn test_drop_fcfs_random_probing() {
+    drop_hashmap(OpenAddressingHashTable::<Tracked, Tracked, FcfsRandomProbing>::new());
+}
+
+#[test]
+fn test_drop_lcfs_random_probing() {
+    drop_hashmap(OpenAddressingHashTable::<Tracked, Tracked, LcfsRandomProbing>::new());
+}
+
+#[test]
+fn test_drop_into_iter() {
+    let alive = Rc::new(Cell::new(0));
+    let mut table = OpenAddressingHashTable::<u64, Tracked, FcfsLinearProbing>::new();
+
+    for i in 0..100 {
+        assert!(table.insert(i, Tracked::new(i, &alive)).is_ok());
+    }
+
+    // the rest of the buckets should be dropped with the iterator
+    let mut iter = table.into_iter();
+    drop(iter.by_ref().take(30).collect::<Vec<_>>());
+    assert_eq!(alive.get(), 70);
+
+    drop(iter);
+    assert_eq!(alive.get(), 0);
+}
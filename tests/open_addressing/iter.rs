@@ -0,0 +1,76 @@
+use all_of_hashtable::{
+    open_addressing::{FcfsLinearProbing, OpenAddressingHashTable},
+    HashMap,
+};
+
+#[test]
+fn test_iter() {
+    let mut table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::new();
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i * 2), Ok(()));
+    }
+
+    // leave tombstones to be skipped
+    for i in (0..1000).step_by(2) {
+        assert_eq!(table.remove(&i), Ok(i * 2));
+    }
+
+    let mut entries = table.iter().collect::<Vec<_>>();
+    entries.sort_unstable();
+    assert_eq!(entries.len(), 500);
+    assert!(entries
+        .iter()
+        .all(|(key, value)| *key % 2 == 1 && **value == **key * 2));
+
+    let mut keys = table.keys().copied().collect::<Vec<_>>();
+    keys.sort_unstable();
+    assert_eq!(keys, (1..1000).step_by(2).collect::<Vec<_>>());
+
+    let sum: u64 = table.values().sum();
+    assert_eq!(sum, (1..1000).step_by(2).map(|i| i * 2).sum());
+
+    assert_eq!((&table).into_iter().count(), 500);
+}
+
+#[test]
+fn test_iter_mut() {
+    let mut table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::new();
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for (key, value) in table.iter_mut() {
+        *value += *key;
+    }
+
+    for value in table.values_mut() {
+        *value += 1;
+    }
+
+    for (_, value) in &mut table {
+        *value += 1;
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&(i * 2 + 2)));
+    }
+}
+
+#[test]
+fn test_into_iter() {
+    let mut table = OpenAddressingHashTable::<u64, String, FcfsLinearProbing>::new();
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i.to_string()), Ok(()));
+    }
+
+    let mut entries = table.into_iter().collect::<Vec<_>>();
+    entries.sort_unstable();
+
+    assert_eq!(
+        entries,
+        (0..1000).map(|i| (i, i.to_string())).collect::<Vec<_>>()
+    );
+}
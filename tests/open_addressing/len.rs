@@ -0,0 +1,124 @@
+use all_of_hashtable::{
+    open_addressing::{
+        DoubleHashing, Fcfs, FcfsLinearProbing, LcfsLinearProbing, OpenAddressingHashTable,
+        RobinHoodLinearProbing,
+    },
+    HashMap, INITIAL_SIZE, LOAD_FACTOR,
+};
+use std::{
+    collections::hash_map::{DefaultHasher, RandomState},
+    hash::BuildHasherDefault,
+};
+
+#[test]
+fn test_len() {
+    let mut table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::new();
+    assert!(table.is_empty());
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+        assert_eq!(table.len(), i as usize + 1);
+    }
+
+    // failed operations do not change the count
+    assert_eq!(table.insert(0, 0), Err(0));
+    assert_eq!(table.remove(&1000), Err(()));
+    assert_eq!(table.len(), 1000);
+
+    for i in 0..500 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+    assert_eq!(table.len(), 500);
+    assert_eq!(table.tombstones(), 500);
+
+    for i in 500..1000 {
+        assert_eq!(table.entry(i).remove(), Some(i));
+    }
+    assert!(table.is_empty());
+    assert_eq!(table.tombstones(), 1000);
+
+    table.clear();
+    assert!(table.is_empty());
+    assert_eq!(table.tombstones(), 0);
+}
+
+#[test]
+fn test_tombstones_reused() {
+    let mut table = OpenAddressingHashTable::<u64, u64, LcfsLinearProbing>::new();
+
+    // stay under the load factor with the tombstones, so the table is not rehashed
+    for i in 0..4 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..4 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+    assert_eq!(table.tombstones(), 4);
+
+    // the same keys walk over the same path, and fill the tombstones again
+    for i in 0..4 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.len(), 4);
+    assert_eq!(table.tombstones(), 0);
+}
+
+#[test]
+fn test_no_tombstones_on_backshift() {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsLinearProbing::new(false),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+        assert_eq!(table.tombstones(), 0);
+    }
+    assert!(table.is_empty());
+
+    let mut table = OpenAddressingHashTable::<u64, u64, RobinHoodLinearProbing>::new();
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+        assert_eq!(table.tombstones(), 0);
+    }
+    assert!(table.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "backward shift needs a contiguous probe sequence")]
+fn test_backshift_on_non_contiguous_probe() {
+    // the backward shift needs the linear probing, so the other probes reject `tombstone: false`
+    let _ = Fcfs::with_probe(DoubleHashing::<RandomState>::default(), false);
+}
+
+#[test]
+fn test_churn_does_not_grow() {
+    let mut table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::new();
+
+    for i in 0..100 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    let capacity = table.capacity();
+
+    // the live entries stay at 100, so the tombstones should be rehashed away, not doubled
+    for i in 100..100_000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+        assert_eq!(table.remove(&(i - 100)), Ok(i - 100));
+    }
+
+    assert_eq!(table.len(), 100);
+    assert!(table.capacity() <= capacity * 2);
+}
@@ -1,8 +1,10 @@
 use all_of_hashtable::{
-    open_addressing::{FcfsLinearProbing, LcfsLinearProbing, OpenAddressingHashTable},
+    open_addressing::{
+        FcfsLinearProbing, LcfsLinearProbing, OpenAddressingHashTable, RobinHoodLinearProbing,
+    },
     HashMap, INITIAL_SIZE, LOAD_FACTOR,
 };
-use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault, thread};
 
 use crate::util::stress_hashmap;
 
@@ -16,7 +18,7 @@ fn test_crd_fcfs() {
     );
 
     for i in 0..1000 {
-        assert_eq!(table.insert(&i, i), Ok(()));
+        assert_eq!(table.insert(i, i), Ok(()));
     }
 
     for i in 0..1000 {
@@ -54,7 +56,7 @@ fn test_crd_lcfs() {
     );
 
     for i in 0..1000 {
-        assert_eq!(table.insert(&i, i), Ok(()));
+        assert_eq!(table.insert(i, i), Ok(()));
     }
 
     for i in 0..1000 {
@@ -81,3 +83,144 @@ fn test_stress_lcfs() {
 
     stress_hashmap(table, 100_000);
 }
+
+#[test]
+fn test_crd_fcfs_backshift() {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsLinearProbing::new(false),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress_fcfs_backshift() {
+    let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsLinearProbing::new(false),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_crd_lcfs_backshift() {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsLinearProbing::new(false),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress_lcfs_backshift() {
+    let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsLinearProbing::new(false),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_crd_robin_hood() {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        RobinHoodLinearProbing,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress_robin_hood() {
+    let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        RobinHoodLinearProbing,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_concurrent_lookup() {
+    // the lookup by `&self` only reads the buckets, so the threads can share the table
+    let mut table = OpenAddressingHashTable::<u64, u64, FcfsLinearProbing>::new();
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in (0..1000).step_by(2) {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    let table = &table;
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(move || {
+                for i in 0..1000 {
+                    let expected = if i % 2 == 0 { None } else { Some(&i) };
+                    assert_eq!(table.lookup(&i), expected);
+                }
+            });
+        }
+    });
+}
@@ -1,3 +1,14 @@
+mod borrow;
+mod capacity;
+mod drop;
+mod iter;
+mod len;
 mod linear_probing;
+mod owned;
 mod quadratic_probing;
 mod double_hashing;
+mod probe_sequence;
+mod random_probing;
+mod set;
+mod stats;
+mod tombstone;
@@ -0,0 +1,42 @@
+use all_of_hashtable::{
+    open_addressing::{
+        FcfsDoubleHashing, FcfsLinearProbing, LcfsLinearProbing, OpenAddressingHashTable,
+        RobinHoodLinearProbing,
+    },
+    HashMap,
+};
+
+use crate::util::{owned_hashmap, Unique};
+
+#[test]
+fn test_owned_fcfs_linear_probing() {
+    owned_hashmap(OpenAddressingHashTable::<Unique, u64, FcfsLinearProbing>::new());
+}
+
+#[test]
+fn test_owned_lcfs_linear_probing() {
+    owned_hashmap(OpenAddressingHashTable::<Unique, u64, LcfsLinearProbing>::new());
+}
+
+#[test]
+fn test_owned_robin_hood_linear_probing() {
+    owned_hashmap(OpenAddressingHashTable::<Unique, u64, RobinHoodLinearProbing>::new());
+}
+
+#[test]
+fn test_owned_fcfs_double_hashing() {
+    owned_hashmap(OpenAddressingHashTable::<Unique, u64, FcfsDoubleHashing>::new());
+}
+
+// the reference-taking methods are kept for the migration, and clone the key
+#[test]
+#[allow(deprecated)]
+fn test_insert_cloned() {
+    let mut table = OpenAddressingHashTable::<String, u64, FcfsLinearProbing>::new();
+    let key = "key".to_string();
+
+    assert_eq!(table.insert_cloned(&key, 0), Ok(()));
+    assert_eq!(table.insert_cloned(&key, 1), Err(1));
+    assert_eq!(table.insert_or_replace_cloned(&key, 1), Some(0));
+    assert_eq!(table.lookup(&key), Some(&1));
+}
@@ -0,0 +1,43 @@
+use all_of_hashtable::{
+    open_addressing::{Fcfs, Lcfs, OpenAddressingHashTable, ProbeSequence},
+    INITIAL_SIZE, LOAD_FACTOR,
+};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault, hash::Hash};
+
+use crate::util::stress_hashmap;
+
+// the user-defined probe sequence stepping by 3, which is co-prime with the power-of-two size
+#[derive(Default)]
+struct StepProbing;
+
+impl ProbeSequence for StepProbing {
+    type Probe = std::iter::StepBy<std::ops::RangeFrom<usize>>;
+
+    fn probe<K: ?Sized + Hash>(&self, _: &K, _: usize) -> Self::Probe {
+        (3..).step_by(3)
+    }
+}
+
+#[test]
+fn test_stress_custom_fcfs() {
+    let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        Fcfs::with_probe(StepProbing, true),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_stress_custom_lcfs() {
+    let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        Lcfs::with_probe(StepProbing, true),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
@@ -1,10 +1,13 @@
 use all_of_hashtable::{
-    open_addressing::{FcfsQuadraticProbing, OpenAddressingHashTable},
+    open_addressing::{
+        FcfsQuadraticProbing, Lcfs, LcfsQuadraticProbing, OpenAddressingHashTable, QuadraticMode,
+        QuadraticProbing,
+    },
     HashMap, INITIAL_SIZE, LOAD_FACTOR,
 };
 use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
 
-use crate::util::stress_hashmap;
+use crate::util::{stress_hashmap, IdentityHasher};
 
 #[test]
 fn test_crd_fcfs() {
@@ -16,7 +19,7 @@ fn test_crd_fcfs() {
     );
 
     for i in 0..1000 {
-        assert_eq!(table.insert(&i, i), Ok(()));
+        assert_eq!(table.insert(i, i), Ok(()));
     }
 
     for i in 0..1000 {
@@ -43,3 +46,171 @@ fn test_stress_fcfs() {
 
     stress_hashmap(table, 100_000);
 }
+
+#[test]
+fn test_crd_lcfs() {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsQuadraticProbing::new(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress_lcfs() {
+    let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsQuadraticProbing::new(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_triangular_permutation() {
+    for n in 1..=20 {
+        let size = 1usize << n;
+        let mut visited = vec![false; size];
+
+        for step in 0..size {
+            let index = QuadraticMode::Triangular.offset(step) & (size - 1);
+
+            assert!(!visited[index], "the size {} visits {} twice.", size, index);
+            visited[index] = true;
+        }
+    }
+}
+
+#[test]
+fn test_classic_not_permutation() {
+    for n in 2..=20 {
+        let size = 1usize << n;
+        let mut visited = vec![false; size];
+
+        for step in 0..size {
+            visited[QuadraticMode::Classic.offset(step) & (size - 1)] = true;
+        }
+
+        assert!(visited.iter().any(|visited| !visited));
+    }
+}
+
+#[test]
+fn test_crd_fcfs_modes() {
+    for mode in [
+        QuadraticMode::Classic,
+        QuadraticMode::Triangular,
+        QuadraticMode::Alternating,
+    ] {
+        let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+            BuildHasherDefault::<DefaultHasher>::default(),
+            FcfsQuadraticProbing::with_mode(mode),
+            INITIAL_SIZE,
+            LOAD_FACTOR,
+        );
+
+        for i in 0..1000 {
+            assert_eq!(table.insert(i, i), Ok(()));
+        }
+
+        for i in 0..1000 {
+            assert_eq!(table.lookup(&i), Some(&i));
+        }
+
+        for i in 0..1000 {
+            assert_eq!(table.remove(&i), Ok(i));
+        }
+
+        for i in 0..1000 {
+            assert_eq!(table.lookup(&i), None);
+        }
+    }
+}
+
+#[test]
+fn test_fill_without_resize() {
+    // the triangular probing finds the last empty bucket, so the full load does not resize
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsQuadraticProbing::new(),
+        1024,
+        1.0,
+    );
+
+    for i in 0..1024 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), 1024);
+
+    for i in 0..1024 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+}
+
+#[test]
+fn test_fill_without_resize_lcfs() {
+    // the evicted buckets follow their own probe sequences until the last empty bucket
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsQuadraticProbing::new(),
+        1024,
+        1.0,
+    );
+
+    for i in 0..1024 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), 1024);
+
+    for i in 0..1024 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1024 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+    assert!(table.is_empty());
+}
+
+#[test]
+fn test_lcfs_classic_no_free_bucket() {
+    // the classic sequence visits the offsets 1, 4, 9 and 0 modulo 16 only
+    let mut table = OpenAddressingHashTable::<u64, u64, _, _>::new_with_properties(
+        BuildHasherDefault::<IdentityHasher>::default(),
+        Lcfs::with_probe(QuadraticProbing::new(QuadraticMode::Classic), true),
+        16,
+        1.0,
+    );
+
+    // 20 takes the home 4, and evicts 4 to 5
+    for key in [4, 20, 8, 13] {
+        assert_eq!(table.insert(key, key), Ok(()));
+    }
+
+    // 21 finds 6 free, but 4 evicted from 5 only reaches 8, 13 and 4, so the table grows
+    assert_eq!(table.insert(21, 21), Ok(()));
+    assert_eq!(table.capacity(), 32);
+
+    for key in [4, 20, 8, 13, 21] {
+        assert_eq!(table.lookup(&key), Some(&key));
+    }
+}
@@ -0,0 +1,86 @@
+use all_of_hashtable::{
+    open_addressing::{FcfsRandomProbing, LcfsRandomProbing, OpenAddressingHashTable},
+    HashMap, INITIAL_SIZE, LOAD_FACTOR,
+};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+use crate::util::stress_hashmap;
+
+#[test]
+fn test_crd_fcfs() {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsRandomProbing::new(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress_fcfs() {
+    let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsRandomProbing::new(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_stress_lcfs() {
+    let table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsRandomProbing::new(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_fill_without_resize() {
+    // the generator has the full period, so the full load does not resize
+    let mut fcfs = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsRandomProbing::new(),
+        1024,
+        1.0,
+    );
+    let mut lcfs = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsRandomProbing::new(),
+        1024,
+        1.0,
+    );
+
+    for i in 0..1024 {
+        assert_eq!(fcfs.insert(i, i), Ok(()));
+        assert_eq!(lcfs.insert(i, i), Ok(()));
+    }
+    assert_eq!(fcfs.capacity(), 1024);
+    assert_eq!(lcfs.capacity(), 1024);
+
+    for i in 0..1024 {
+        assert_eq!(fcfs.lookup(&i), Some(&i));
+        assert_eq!(lcfs.lookup(&i), Some(&i));
+    }
+}
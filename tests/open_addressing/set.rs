@@ -0,0 +1,183 @@
+use all_of_hashtable::{
+    open_addressing::{
+        Bucket, FcfsDoubleHashing, FcfsLinearProbing, FcfsQuadraticProbing, FcfsRandomProbing,
+        LcfsDoubleHashing, LcfsLinearProbing, LcfsQuadraticProbing, LcfsRandomProbing,
+        OpenAddressingHashSet, RobinHoodLinearProbing,
+    },
+    EntryStrategy, HashSet, INITIAL_SIZE, LOAD_FACTOR,
+};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+type Set<E> = OpenAddressingHashSet<u64, E, BuildHasherDefault<DefaultHasher>>;
+
+fn set_of<E>(entry: E, keys: impl IntoIterator<Item = u64>) -> Set<E>
+where
+    E: EntryStrategy<u64, Bucket<u64, ()>>,
+{
+    let mut set = Set::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        entry,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for key in keys {
+        assert_eq!(set.insert(key), Ok(()));
+    }
+
+    set
+}
+
+fn sorted<'a>(keys: impl Iterator<Item = &'a u64>) -> Vec<u64> {
+    let mut keys = keys.copied().collect::<Vec<_>>();
+    keys.sort_unstable();
+    keys
+}
+
+fn crd_hashset<E>(entry: E)
+where
+    E: EntryStrategy<u64, Bucket<u64, ()>>,
+{
+    // resize moves the buckets
+    let mut set = set_of(entry, 0..1000);
+    assert_eq!(set.len(), 1000);
+    assert_eq!(set.insert(0), Err(0));
+
+    for i in 0..1000 {
+        assert!(set.contains(&i));
+    }
+    assert!(!set.contains(&1000));
+
+    for i in (0..1000).step_by(2) {
+        assert_eq!(set.remove(&i), Ok(i));
+        assert_eq!(set.remove(&i), Err(()));
+    }
+
+    assert_eq!(set.len(), 500);
+    assert_eq!(sorted(set.iter()), (1..1000).step_by(2).collect::<Vec<_>>());
+
+    let mut keys = set.into_iter().collect::<Vec<_>>();
+    keys.sort_unstable();
+    assert_eq!(keys, (1..1000).step_by(2).collect::<Vec<_>>());
+}
+
+fn algebra_hashset<E>(entry: E, other_entry: E)
+where
+    E: EntryStrategy<u64, Bucket<u64, ()>>,
+{
+    let set = set_of(entry, 0..100);
+    let other = set_of(other_entry, 50..150);
+
+    assert_eq!(sorted(set.union(&other)), (0..150).collect::<Vec<_>>());
+    assert_eq!(
+        sorted(set.intersection(&other)),
+        (50..100).collect::<Vec<_>>()
+    );
+    assert_eq!(sorted(set.difference(&other)), (0..50).collect::<Vec<_>>());
+    assert_eq!(
+        sorted(other.difference(&set)),
+        (100..150).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        sorted(set.symmetric_difference(&other)),
+        (0..50).chain(100..150).collect::<Vec<_>>()
+    );
+
+    let subset = set.intersection(&other).copied().collect::<Set<E>>();
+    assert!(subset.is_subset(&set));
+    assert!(subset.is_subset(&other));
+    assert!(set.is_subset(&set));
+    assert!(!set.is_subset(&other));
+    assert!(!set.is_subset(&subset));
+}
+
+#[test]
+fn test_set_fcfs_linear_probing() {
+    crd_hashset(FcfsLinearProbing::default());
+    algebra_hashset(FcfsLinearProbing::default(), FcfsLinearProbing::default());
+}
+
+#[test]
+fn test_set_fcfs_backshift_linear_probing() {
+    crd_hashset(FcfsLinearProbing::new(false));
+    algebra_hashset(FcfsLinearProbing::new(false), FcfsLinearProbing::new(false));
+}
+
+#[test]
+fn test_set_lcfs_linear_probing() {
+    crd_hashset(LcfsLinearProbing::default());
+    algebra_hashset(LcfsLinearProbing::default(), LcfsLinearProbing::default());
+}
+
+#[test]
+fn test_set_robin_hood_linear_probing() {
+    crd_hashset(RobinHoodLinearProbing);
+    algebra_hashset(RobinHoodLinearProbing, RobinHoodLinearProbing);
+}
+
+#[test]
+fn test_set_fcfs_quadratic_probing() {
+    crd_hashset(FcfsQuadraticProbing::default());
+    algebra_hashset(
+        FcfsQuadraticProbing::default(),
+        FcfsQuadraticProbing::default(),
+    );
+}
+
+#[test]
+fn test_set_lcfs_quadratic_probing() {
+    crd_hashset(LcfsQuadraticProbing::default());
+    algebra_hashset(
+        LcfsQuadraticProbing::default(),
+        LcfsQuadraticProbing::default(),
+    );
+}
+
+#[test]
+fn test_set_fcfs_double_hashing() {
+    crd_hashset(<FcfsDoubleHashing>::default());
+    algebra_hashset(
+        <FcfsDoubleHashing>::default(),
+        <FcfsDoubleHashing>::default(),
+    );
+}
+
+#[test]
+fn test_set_lcfs_double_hashing() {
+    crd_hashset(<LcfsDoubleHashing>::default());
+    algebra_hashset(
+        <LcfsDoubleHashing>::default(),
+        <LcfsDoubleHashing>::default(),
+    );
+}
+
+#[test]
+fn test_set_fcfs_random_probing() {
+    crd_hashset(<FcfsRandomProbing>::default());
+    algebra_hashset(
+        <FcfsRandomProbing>::default(),
+        <FcfsRandomProbing>::default(),
+    );
+}
+
+#[test]
+fn test_set_lcfs_random_probing() {
+    crd_hashset(<LcfsRandomProbing>::default());
+    algebra_hashset(
+        <LcfsRandomProbing>::default(),
+        <LcfsRandomProbing>::default(),
+    );
+}
+
+#[test]
+fn test_set_borrow() {
+    let mut set = ["a", "b", "c"]
+        .iter()
+        .map(|key| key.to_string())
+        .collect::<OpenAddressingHashSet<String, FcfsLinearProbing>>();
+
+    assert!(set.contains("a"));
+    assert_eq!(set.remove("b"), Ok("b".to_string()));
+    assert!(!set.contains("b"));
+    assert_eq!(set.len(), 2);
+}
@@ -0,0 +1,140 @@
+use all_of_hashtable::{
+    open_addressing::{
+        Bucket, FcfsDoubleHashing, FcfsLinearProbing, FcfsQuadraticProbing, LcfsLinearProbing,
+        OpenAddressingHashTable, ProbeStats, RobinHoodLinearProbing,
+    },
+    EntryStrategy, HashMap, INITIAL_SIZE, LOAD_FACTOR,
+};
+use rand::{thread_rng, Rng};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+use crate::util::IdentityHasher;
+
+fn clustered_table<E>(
+    entry: E,
+) -> OpenAddressingHashTable<u64, u64, E, BuildHasherDefault<IdentityHasher>>
+where
+    E: EntryStrategy<u64, Bucket<u64, u64>>,
+{
+    let mut table = OpenAddressingHashTable::new_with_properties(
+        BuildHasherDefault::<IdentityHasher>::default(),
+        entry,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    // the key 8 collides with 0, and is placed after the cluster of 0..3
+    for key in [0, 1, 2, 8] {
+        assert_eq!(table.insert(key, key), Ok(()));
+    }
+
+    table
+}
+
+#[test]
+fn test_stats_linear_probing() {
+    let mut table = clustered_table(FcfsLinearProbing::default());
+    let stats = table.stats();
+
+    assert_eq!(
+        stats.successful,
+        ProbeStats {
+            average: 1.75,
+            max: 4,
+            variance: 1.6875,
+            histogram: vec![3, 0, 0, 1],
+        }
+    );
+
+    // the homes on the cluster walk to its end
+    assert_eq!(stats.unsuccessful.average, 18.0 / 8.0);
+    assert_eq!(stats.unsuccessful.max, 5);
+    assert_eq!(stats.unsuccessful.histogram, vec![4, 1, 1, 1, 1]);
+
+    assert_eq!(stats.clusters, vec![0, 0, 0, 1]);
+    assert_eq!(stats.tombstone_ratio, 0.0);
+
+    // the tombstone keeps the cluster
+    assert_eq!(table.remove(&1), Ok(1));
+    let stats = table.stats();
+
+    assert_eq!(stats.successful.histogram, vec![2, 0, 0, 1]);
+    assert_eq!(stats.clusters, vec![0, 0, 0, 1]);
+    assert_eq!(stats.tombstone_ratio, 1.0 / 8.0);
+}
+
+#[test]
+fn test_stats_robin_hood() {
+    let table = clustered_table(RobinHoodLinearProbing);
+    let stats = table.stats();
+
+    // the key 8 takes the place of 1, which is closer to its home
+    assert_eq!(stats.successful.histogram, vec![1, 3]);
+
+    // the unsuccessful lookup stops on the bucket closer to its home than the key
+    assert_eq!(stats.unsuccessful.average, 15.0 / 8.0);
+    assert_eq!(stats.unsuccessful.histogram, vec![4, 1, 3]);
+    assert_eq!(stats.clusters, vec![0, 0, 0, 1]);
+}
+
+#[test]
+fn test_stats_empty() {
+    let table = OpenAddressingHashTable::<u64, u64, LcfsLinearProbing>::new();
+    let stats = table.stats();
+
+    assert_eq!(stats.successful, ProbeStats::default());
+    assert_eq!(stats.unsuccessful.average, 1.0);
+    assert_eq!(stats.unsuccessful.histogram, vec![INITIAL_SIZE]);
+    assert!(stats.clusters.is_empty());
+}
+
+fn check_totals<E: EntryStrategy<u64, Bucket<u64, u64>>>(entry: E) {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        entry,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+    let mut rng = thread_rng();
+
+    for _ in 0..1000 {
+        let key = rng.gen();
+        let _ = table.insert(key, key);
+
+        if rng.gen_range(0..4) == 0 {
+            let _ = table.remove(&key);
+        }
+    }
+
+    let size = ((table.capacity() as f32 / LOAD_FACTOR) as usize).next_power_of_two();
+    let stats = table.stats();
+
+    // every key and every home is walked once
+    assert_eq!(
+        stats.successful.histogram.iter().sum::<usize>(),
+        table.len()
+    );
+    assert_eq!(stats.unsuccessful.histogram.iter().sum::<usize>(), size);
+
+    // the clusters cover the occupied buckets
+    let occupied: usize = stats
+        .clusters
+        .iter()
+        .enumerate()
+        .map(|(i, count)| (i + 1) * count)
+        .sum();
+    assert_eq!(occupied, table.len() + table.tombstones());
+    assert_eq!(
+        stats.tombstone_ratio,
+        table.tombstones() as f64 / size as f64
+    );
+}
+
+#[test]
+fn test_stats_totals() {
+    check_totals(FcfsLinearProbing::default());
+    check_totals(FcfsQuadraticProbing::default());
+    check_totals(FcfsDoubleHashing::new());
+    check_totals(LcfsLinearProbing::default());
+    check_totals(RobinHoodLinearProbing);
+}
@@ -0,0 +1,103 @@
+use all_of_hashtable::{
+    open_addressing::{
+        Bucket, FcfsDoubleHashing, FcfsLinearProbing, FcfsQuadraticProbing, FcfsRandomProbing,
+        LcfsDoubleHashing, LcfsLinearProbing, OpenAddressingHashTable,
+    },
+    EntryStrategy, HashMap, INITIAL_SIZE, LOAD_FACTOR, TOMBSTONE_FACTOR,
+};
+use rand::{prelude::SliceRandom, thread_rng};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+use crate::util::stress_hashmap;
+
+fn rehash_in_place<E: EntryStrategy<u64, Bucket<u64, u64>>>(entry: E) {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        entry,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    let mut keys: Vec<u64> = (0..1000).collect();
+    for key in &keys {
+        assert_eq!(table.insert(*key, *key), Ok(()));
+    }
+
+    keys.shuffle(&mut thread_rng());
+    let (removed, remained) = keys.split_at(500);
+
+    for key in removed {
+        assert_eq!(table.remove(key), Ok(*key));
+    }
+    assert_eq!(table.tombstones(), 500);
+
+    let capacity = table.capacity();
+    table.rehash_in_place();
+    assert_eq!(table.tombstones(), 0);
+    assert_eq!(table.capacity(), capacity);
+    assert_eq!(table.len(), 500);
+
+    for key in remained {
+        assert_eq!(table.lookup(key), Some(key));
+    }
+
+    for key in removed {
+        assert_eq!(table.lookup(key), None);
+    }
+}
+
+#[test]
+fn test_rehash_in_place() {
+    rehash_in_place(FcfsLinearProbing::default());
+    rehash_in_place(FcfsQuadraticProbing::default());
+    rehash_in_place(FcfsDoubleHashing::new());
+    rehash_in_place(FcfsRandomProbing::new());
+    rehash_in_place(LcfsLinearProbing::default());
+    rehash_in_place(LcfsDoubleHashing::new());
+}
+
+#[test]
+fn test_tombstone_factor() {
+    // the table holds all keys without resizing
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsLinearProbing::default(),
+        128,
+        LOAD_FACTOR,
+    );
+    table.set_tombstone_factor(TOMBSTONE_FACTOR);
+
+    for round in 0..10 {
+        for i in 0..64 {
+            assert_eq!(table.insert(round * 64 + i, i), Ok(()));
+        }
+
+        for i in 0..64 {
+            assert_eq!(table.remove(&(round * 64 + i)), Ok(i));
+            assert!(table.tombstones() as f32 <= 128.0 * TOMBSTONE_FACTOR);
+        }
+    }
+
+    assert_eq!(table.capacity(), (128.0 * LOAD_FACTOR) as usize);
+}
+
+#[test]
+fn test_stress_tombstone_factor() {
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        FcfsQuadraticProbing::default(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+    table.set_tombstone_factor(TOMBSTONE_FACTOR);
+    stress_hashmap(table, 100_000);
+
+    let mut table = OpenAddressingHashTable::<u64, u64, _>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        LcfsDoubleHashing::new(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+    table.set_tombstone_factor(TOMBSTONE_FACTOR);
+    stress_hashmap(table, 100_000);
+}
@@ -0,0 +1,119 @@
+use all_of_hashtable::{
+    open_addressing::{FcfsDoubleHashing, FcfsLinearProbing, RobinHoodLinearProbing},
+    sharded::ShardedHashMap,
+    ConcurrentHashMap, ConcurrentHashMapMut, INITIAL_SIZE, LOAD_FACTOR,
+};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault, thread};
+
+use crate::util::Unique;
+
+const THREADS: u64 = 8;
+
+#[test]
+fn test_crd() {
+    let map = ShardedHashMap::<u64, u64, FcfsLinearProbing>::new();
+
+    for i in 0..1000 {
+        assert_eq!(map.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(map.lookup_cloned(&i), Some(i));
+        assert_eq!(map.insert(i, i), Err(i));
+    }
+    assert_eq!(map.len(), 1000);
+
+    for i in 0..1000 {
+        *map.lookup_mut(&i).unwrap() += 1;
+        assert_eq!(map.insert_or_replace(i, i + 2), Some(i + 1));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(map.remove(&i), Ok(i + 2));
+    }
+
+    for i in 0..1000 {
+        assert!(map.lookup(&i).is_none());
+    }
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_single_shard() {
+    let map = ShardedHashMap::<u64, u64, FcfsLinearProbing>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        1,
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(map.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(*map.lookup(&i).unwrap(), i);
+    }
+}
+
+#[test]
+fn test_owned() {
+    // the key is moved into the shard, so it need not be cloned
+    let map = ShardedHashMap::<Unique, u64, FcfsLinearProbing>::new();
+
+    for i in 0..1000 {
+        assert_eq!(map.insert(Unique(i), i), Ok(()));
+    }
+
+    assert_eq!(map.insert(Unique(0), 1), Err(1));
+    assert_eq!(map.insert_or_replace(Unique(0), 1), Some(0));
+    assert_eq!(map.lookup_cloned(&Unique(0)), Some(1));
+    assert_eq!(map.len(), 1000);
+}
+
+#[test]
+fn test_stress_fcfs_linear_probing() {
+    crate::util::stress_concurrent(
+        ShardedHashMap::<u64, u64, FcfsLinearProbing>::new(),
+        100_000,
+    );
+}
+
+#[test]
+fn test_stress_fcfs_double_hashing() {
+    crate::util::stress_concurrent(
+        ShardedHashMap::<u64, u64, FcfsDoubleHashing>::new(),
+        100_000,
+    );
+}
+
+#[test]
+fn test_stress_robin_hood() {
+    crate::util::stress_concurrent(
+        ShardedHashMap::<u64, u64, RobinHoodLinearProbing>::new(),
+        100_000,
+    );
+}
+
+#[test]
+fn test_concurrent_counter() {
+    // the increments on the shared keys are not lost by the write lock
+    let map = ShardedHashMap::<u64, u64, FcfsLinearProbing>::new();
+
+    for key in 0..16 {
+        assert_eq!(map.insert(key, 0), Ok(()));
+    }
+
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for i in 0..10_000 {
+                    *map.lookup_mut(&(i % 16)).unwrap() += 1;
+                }
+            });
+        }
+    });
+
+    let total: u64 = (0..16).map(|key| map.lookup_cloned(&key).unwrap()).sum();
+    assert_eq!(total, THREADS * 10_000);
+}
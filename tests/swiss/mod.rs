@@ -0,0 +1,163 @@
+use all_of_hashtable::{
+    swiss::{SwissHashSet, SwissTable},
+    HashMap, INITIAL_SIZE, LOAD_FACTOR, SHRINK_LOAD_FACTOR,
+};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+use crate::util::{
+    algebra_hashset, borrow_hashmap, crd_hashset, drop_hashmap, owned_hashmap, stress_hashmap,
+    Tracked, Unique,
+};
+
+#[test]
+fn test_crd() {
+    let mut table = SwissTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    for i in 0..1000 {
+        assert_eq!(table.lookup(&i), None);
+    }
+}
+
+#[test]
+fn test_stress() {
+    let table = SwissTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        INITIAL_SIZE,
+        LOAD_FACTOR,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_stress_full_load() {
+    // an empty slot is always left to stop the probe
+    let table = SwissTable::<u64, u64>::new_with_properties(
+        BuildHasherDefault::<DefaultHasher>::default(),
+        INITIAL_SIZE,
+        1.0,
+    );
+
+    stress_hashmap(table, 100_000);
+}
+
+#[test]
+fn test_tombstones() {
+    let mut table = SwissTable::<u64, u64>::with_capacity(1000);
+    let capacity = table.capacity();
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    // the removal leaves the tombstone only if a probe may have passed the slot
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+        assert!(table.tombstones() <= i as usize + 1);
+    }
+    assert_eq!(table.len(), 0);
+
+    // the churn reuses the tombstones, or cleans them up on the same size
+    for i in 1000..100_000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+    assert_eq!(table.capacity(), capacity);
+}
+
+#[test]
+fn test_iter() {
+    let mut table = SwissTable::<u64, u64>::new();
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+
+    for (_, value) in table.iter_mut() {
+        *value *= 2;
+    }
+
+    let mut entries = table.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+    entries.sort_unstable();
+    assert_eq!(entries, (0..1000).map(|i| (i, i * 2)).collect::<Vec<_>>());
+
+    let mut entries = table.into_iter().collect::<Vec<_>>();
+    entries.sort_unstable();
+    assert_eq!(entries, (0..1000).map(|i| (i, i * 2)).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_capacity() {
+    let mut table = SwissTable::<u64, u64>::with_capacity(1000);
+    let capacity = table.capacity();
+    assert!(capacity >= 1000);
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    assert_eq!(table.capacity(), capacity);
+
+    for i in 0..1000 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+
+    table.shrink_to_fit();
+    assert!(table.capacity() < capacity);
+}
+
+#[test]
+fn test_auto_shrink() {
+    let mut table = SwissTable::<u64, u64>::new();
+    table.set_shrink_load_factor(SHRINK_LOAD_FACTOR);
+
+    for i in 0..1000 {
+        assert_eq!(table.insert(i, i), Ok(()));
+    }
+    let capacity = table.capacity();
+
+    for i in 0..990 {
+        assert_eq!(table.remove(&i), Ok(i));
+    }
+    assert!(table.capacity() < capacity);
+
+    for i in 990..1000 {
+        assert_eq!(table.lookup(&i), Some(&i));
+    }
+}
+
+#[test]
+fn test_drop() {
+    drop_hashmap(SwissTable::<Tracked, Tracked>::new());
+}
+
+#[test]
+fn test_borrow() {
+    borrow_hashmap(SwissTable::<String, u64>::new());
+}
+
+#[test]
+fn test_owned() {
+    owned_hashmap(SwissTable::<Unique, u64>::new());
+}
+
+#[test]
+fn test_set() {
+    crd_hashset::<SwissHashSet<u64>>();
+    algebra_hashset::<SwissHashSet<u64>>();
+}
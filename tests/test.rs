@@ -1,2 +1,10 @@
 pub mod util;
+mod chaining;
+mod cuckoo;
+mod hopscotch;
+mod lock_free;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod open_addressing;
+mod sharded;
+mod swiss;
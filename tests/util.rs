@@ -1,6 +1,13 @@
-use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+use std::{
+    cell::Cell,
+    collections::hash_map::DefaultHasher,
+    hash::{BuildHasherDefault, Hash, Hasher},
+    iter::FromIterator,
+    rc::Rc,
+    thread,
+};
 
-use all_of_hashtable::HashMap;
+use all_of_hashtable::{ConcurrentHashMap, HashMap, HashSet};
 use rand::{thread_rng, prelude::{ThreadRng, SliceRandom}, Rng};
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -8,6 +15,7 @@ enum Operation {
     Insert,
     Lookup,
     Remove,
+    Entry,
 }
 
 #[derive(PartialEq)]
@@ -34,7 +42,12 @@ where
         Err(())
     };
 
-    let ops = [Operation::Insert, Operation::Lookup, Operation::Remove];
+    let ops = [
+        Operation::Insert,
+        Operation::Lookup,
+        Operation::Remove,
+        Operation::Entry,
+    ];
     let types = [OperationType::Some, OperationType::None];
 
     let mut ref_map = std::collections::HashMap::new();
@@ -45,7 +58,7 @@ where
         let ref_map_keys = ref_map.keys().collect::<Vec<&u64>>();
         let existing_key = ref_map_keys.choose(&mut rng);
         
-        if existing_key.is_none() || *t == OperationType::None {
+        if *t == OperationType::None || existing_key.is_none() {
             // run operation with not existing key
             let not_existing_key = if let Ok(key) = gen_not_existing_key(&mut rng, &ref_map) {
                 key
@@ -62,14 +75,20 @@ where
                         "[{:0>10}] InsertNone: ({:?}, {})",
                         i, not_existing_key, value
                     );
-                    assert_eq!(ref_map.insert(not_existing_key.clone(), value), None);
-                    assert_eq!(map.insert(&not_existing_key, value), Ok(()));
+                    assert_eq!(ref_map.insert(not_existing_key, value), None);
+
+                    if rng.gen() {
+                        assert_eq!(map.insert(not_existing_key, value), Ok(()));
+                    } else {
+                        assert_eq!(map.insert_or_replace(not_existing_key, value), None);
+                    }
                 }
                 Operation::Lookup => {
                     // should fail
                     println!("[{:0>10}] LookupNone: ({:?}, None)", i, not_existing_key);
                     assert_eq!(ref_map.get(&not_existing_key), None);
                     assert_eq!(map.lookup(&not_existing_key), None);
+                    assert_eq!(map.lookup_mut(&not_existing_key), None);
                 }
                 Operation::Remove => {
                     // should fail
@@ -77,30 +96,70 @@ where
                     assert_eq!(ref_map.remove(&not_existing_key), None);
                     assert_eq!(map.remove(&not_existing_key), Err(()));
                 }
+                Operation::Entry => {
+                    // should insert or fail to remove
+                    let value: u64 = rng.gen();
+
+                    if rng.gen() {
+                        println!(
+                            "[{:0>10}] EntryNone: ({:?}, {})",
+                            i, not_existing_key, value
+                        );
+                        assert_eq!(ref_map.insert(not_existing_key, value), None);
+                        assert_eq!(
+                            *map.entry(not_existing_key)
+                                .and_modify(|_| panic!())
+                                .or_insert(value),
+                            value
+                        );
+                    } else {
+                        println!("[{:0>10}] EntryRemoveNone: ({:?})", i, not_existing_key);
+                        assert_eq!(map.entry(not_existing_key).remove(), None);
+                    }
+                }
             }
-        } else {
+        } else if let Some(&&existing_key) = existing_key {
             // run operation with existing key
-            let existing_key = (*existing_key.unwrap()).clone();
 
             match ops.choose(&mut rng).unwrap() {
                 Operation::Insert => {
                     // should fail
                     let value: u64 = rng.gen();
 
-                    println!("[{:0>10}] InsertSome: ({:?}, {})", i, existing_key, value);
-                    assert_eq!(map.insert(&existing_key, value), Err(value));
+                    if rng.gen() {
+                        println!("[{:0>10}] InsertSome: ({:?}, {})", i, existing_key, value);
+                        assert_eq!(map.insert(existing_key, value), Err(value));
+                    } else {
+                        println!("[{:0>10}] ReplaceSome: ({:?}, {})", i, existing_key, value);
+                        let old_value = ref_map.insert(existing_key, value);
+
+                        assert_eq!(map.insert_or_replace(existing_key, value), old_value);
+                    }
                 }
                 Operation::Lookup => {
                     // should success
-                    let value = ref_map.get(&existing_key);
+                    if rng.gen() {
+                        let value = ref_map.get(&existing_key);
 
-                    println!(
-                        "[{:0>10}] LookupSome: ({:?}, {})",
-                        i,
-                        existing_key,
-                        value.unwrap()
-                    );
-                    assert_eq!(map.lookup(&existing_key), value);
+                        println!(
+                            "[{:0>10}] LookupSome: ({:?}, {})",
+                            i,
+                            existing_key,
+                            value.unwrap()
+                        );
+                        assert_eq!(map.lookup(&existing_key), value);
+                    } else {
+                        let value = ref_map.get_mut(&existing_key).unwrap();
+
+                        println!("[{:0>10}] LookupMutSome: ({:?}, {})", i, existing_key, value);
+                        let map_value = map.lookup_mut(&existing_key).unwrap();
+                        assert_eq!(map_value, value);
+
+                        // update in place, and it should be seen on the next lookup
+                        *value = value.wrapping_add(1);
+                        *map_value = map_value.wrapping_add(1);
+                        assert_eq!(map.lookup(&existing_key), Some(&*value));
+                    }
                 }
                 Operation::Remove => {
                     // should success
@@ -119,7 +178,327 @@ where
                     //     assert_eq!(map.lookup(key).is_some(), true, "the key {:?} is not found.", key);
                     // }
                 }
+                Operation::Entry => {
+                    // should update or remove
+                    let value: u64 = rng.gen();
+
+                    if rng.gen() {
+                        println!("[{:0>10}] EntrySome: ({:?}, {})", i, existing_key, value);
+                        let old_value = ref_map.insert(existing_key, value);
+
+                        let mut modified = None;
+                        let entry = map.entry(existing_key).and_modify(|v| modified = Some(*v));
+                        assert_eq!(modified, old_value);
+                        assert_eq!(entry.insert(value), old_value);
+                    } else {
+                        println!("[{:0>10}] EntryRemove: ({:?})", i, existing_key);
+                        let old_value = ref_map.remove(&existing_key);
+
+                        assert_eq!(map.entry(existing_key).remove(), old_value);
+                    }
+                }
             }
         }
+
+        assert_eq!(map.len(), ref_map.len());
+        assert!(map.len() + map.tombstones() <= map.capacity());
+    }
+
+    let mut entries = map
+        .iter()
+        .map(|(key, value)| (*key, *value))
+        .collect::<Vec<_>>();
+    let mut ref_entries = ref_map.into_iter().collect::<Vec<_>>();
+
+    entries.sort_unstable();
+    ref_entries.sort_unstable();
+    assert_eq!(entries, ref_entries);
+}
+
+// each thread runs the random operations on its own keys, against its own reference map
+pub fn stress_concurrent<M>(map: M, iter: u64)
+where
+    M: ConcurrentHashMap<u64, u64>,
+{
+    const THREADS: u64 = 8;
+
+    thread::scope(|scope| {
+        for t in 0..THREADS {
+            let map = &map;
+
+            scope.spawn(move || {
+                let mut rng = thread_rng();
+                let mut ref_map = std::collections::HashMap::new();
+
+                for _ in 0..iter {
+                    let key = rng.gen_range(0..1000) * THREADS + t;
+                    let value: u64 = rng.gen();
+
+                    match rng.gen_range(0..4) {
+                        0 => {
+                            // the failed insert keeps the old value
+                            let inserted = !ref_map.contains_key(&key);
+                            ref_map.entry(key).or_insert(value);
+
+                            assert_eq!(map.insert(key, value).is_ok(), inserted);
+                        }
+                        1 => assert_eq!(map.lookup_cloned(&key), ref_map.get(&key).cloned()),
+                        2 => assert_eq!(map.remove(&key).ok(), ref_map.remove(&key)),
+                        _ => assert_eq!(
+                            map.insert_or_replace(key, value),
+                            ref_map.insert(key, value)
+                        ),
+                    }
+                }
+
+                for (key, value) in ref_map {
+                    assert_eq!(map.lookup_cloned(&key), Some(value));
+                }
+            });
+        }
+    });
+}
+
+fn sorted<'a>(keys: impl Iterator<Item = &'a u64>) -> Vec<u64> {
+    let mut keys = keys.copied().collect::<Vec<_>>();
+    keys.sort_unstable();
+    keys
+}
+
+pub fn crd_hashset<T>()
+where
+    T: HashSet<u64> + FromIterator<u64> + IntoIterator<Item = u64>,
+{
+    // resize moves the buckets
+    let mut set = (0..1000).collect::<T>();
+    assert_eq!(set.len(), 1000);
+    assert_eq!(set.insert(0), Err(0));
+
+    for i in 0..1000 {
+        assert!(set.contains(&i));
+    }
+    assert!(!set.contains(&1000));
+
+    for i in (0..1000).step_by(2) {
+        assert_eq!(set.remove(&i), Ok(i));
+        assert_eq!(set.remove(&i), Err(()));
+    }
+
+    assert_eq!(set.len(), 500);
+    assert_eq!(sorted(set.iter()), (1..1000).step_by(2).collect::<Vec<_>>());
+
+    let mut keys = set.into_iter().collect::<Vec<_>>();
+    keys.sort_unstable();
+    assert_eq!(keys, (1..1000).step_by(2).collect::<Vec<_>>());
+}
+
+pub fn algebra_hashset<T>()
+where
+    T: HashSet<u64> + FromIterator<u64>,
+{
+    let set = (0..100).collect::<T>();
+    let other = (50..150).collect::<T>();
+
+    assert_eq!(sorted(set.union(&other)), (0..150).collect::<Vec<_>>());
+    assert_eq!(
+        sorted(set.intersection(&other)),
+        (50..100).collect::<Vec<_>>()
+    );
+    assert_eq!(sorted(set.difference(&other)), (0..50).collect::<Vec<_>>());
+    assert_eq!(
+        sorted(other.difference(&set)),
+        (100..150).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        sorted(set.symmetric_difference(&other)),
+        (0..50).chain(100..150).collect::<Vec<_>>()
+    );
+
+    let subset = set.intersection(&other).copied().collect::<T>();
+    assert!(subset.is_subset(&set));
+    assert!(subset.is_subset(&other));
+    assert!(set.is_subset(&set));
+    assert!(!set.is_subset(&other));
+    assert!(!set.is_subset(&subset));
+}
+
+/// The value counting its alive instances, to find leaks and double drops
+#[derive(Debug)]
+pub struct Tracked {
+    id: u64,
+    alive: Rc<Cell<usize>>,
+}
+
+impl Tracked {
+    pub fn new(id: u64, alive: &Rc<Cell<usize>>) -> Self {
+        alive.set(alive.get() + 1);
+
+        Self {
+            id,
+            alive: alive.clone(),
+        }
+    }
+}
+
+impl Clone for Tracked {
+    fn clone(&self) -> Self {
+        Self::new(self.id, &self.alive)
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        let alive = self.alive.get();
+
+        assert!(alive > 0, "the id {} is dropped twice.", self.id);
+        self.alive.set(alive - 1);
+    }
+}
+
+impl PartialEq for Tracked {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for Tracked {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+pub fn drop_hashmap<T>(mut map: T)
+where
+    T: HashMap<Tracked, Tracked, BuildHasherDefault<DefaultHasher>>,
+{
+    let keys = Rc::new(Cell::new(0));
+    let values = Rc::new(Cell::new(0));
+    let alive = || (keys.get(), values.get());
+
+    // resize moves the buckets
+    for i in 0..1000 {
+        assert!(map
+            .insert(Tracked::new(i, &keys), Tracked::new(i, &values))
+            .is_ok());
+    }
+    assert_eq!(alive(), (1000, 1000));
+
+    // the failed insert gives the value back
+    assert!(map
+        .insert(Tracked::new(0, &keys), Tracked::new(0, &values))
+        .is_err());
+    assert_eq!(alive(), (1000, 1000));
+
+    assert!(map
+        .insert_or_replace(Tracked::new(1, &keys), Tracked::new(1, &values))
+        .is_some());
+    assert_eq!(alive(), (1000, 1000));
+
+    for i in (0..1000).step_by(2) {
+        assert!(map.remove(&Tracked::new(i, &keys)).is_ok());
+    }
+    assert_eq!(alive(), (500, 500));
+
+    for i in (1..200).step_by(2) {
+        assert!(map.entry(Tracked::new(i, &keys)).remove().is_some());
+    }
+    assert_eq!(alive(), (400, 400));
+
+    map.clear();
+    assert_eq!(alive(), (0, 0));
+
+    for i in 0..100 {
+        assert!(map
+            .insert(Tracked::new(i, &keys), Tracked::new(i, &values))
+            .is_ok());
+    }
+    assert_eq!(alive(), (100, 100));
+
+    drop(map);
+    assert_eq!(alive(), (0, 0));
+}
+
+pub fn borrow_hashmap<T>(mut map: T)
+where
+    T: HashMap<String, u64, BuildHasherDefault<DefaultHasher>>,
+{
+    // resize moves the buckets
+    for i in 0..1000 {
+        assert_eq!(map.insert(i.to_string(), i), Ok(()));
+    }
+
+    // the String keys are looked up by &str without allocation
+    for i in 0..1000 {
+        let key = i.to_string();
+
+        assert!(map.contains_key(key.as_str()));
+        assert_eq!(map.lookup(key.as_str()), Some(&i));
+        *map.lookup_mut(key.as_str()).unwrap() += 1;
+    }
+
+    for i in (0..1000).step_by(2) {
+        assert_eq!(map.remove(i.to_string().as_str()), Ok(i + 1));
+    }
+
+    for i in 0..1000 {
+        let key = i.to_string();
+
+        if i % 2 == 0 {
+            assert!(!map.contains_key(key.as_str()));
+            assert_eq!(map.lookup(key.as_str()), None);
+            assert_eq!(map.remove(key.as_str()), Err(()));
+        } else {
+            assert_eq!(map.lookup(key.as_str()), Some(&(i + 1)));
+        }
+    }
+
+    assert!(!map.contains_key("absent"));
+    assert_eq!(map.len(), 500);
+}
+
+/// The key which cannot be cloned, so the map should move it into the bucket
+#[derive(Debug, PartialEq, Hash)]
+pub struct Unique(pub u64);
+
+pub fn owned_hashmap<T>(mut map: T)
+where
+    T: HashMap<Unique, u64, BuildHasherDefault<DefaultHasher>>,
+{
+    // resize moves the buckets
+    for i in 0..1000 {
+        assert_eq!(map.insert(Unique(i), i), Ok(()));
+    }
+
+    // the failed insert drops the key, and gives the value back
+    assert_eq!(map.insert(Unique(0), 1), Err(1));
+    assert_eq!(map.insert_or_replace(Unique(0), 1), Some(0));
+    assert_eq!(map.insert_or_replace(Unique(1000), 1000), None);
+
+    for i in 1..=1000 {
+        assert_eq!(map.lookup(&Unique(i)), Some(&i));
+    }
+
+    assert_eq!(map.lookup(&Unique(0)), Some(&1));
+    assert_eq!(map.len(), 1001);
+}
+
+// the key itself is the hash, so the home of the key `k` is `k & mask`
+#[derive(Default)]
+pub struct IdentityHasher(u64);
+
+impl Hasher for IdentityHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    // fold the bytes, so that the keys other than u64 still hash
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 << 8) | byte as u64;
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.0 = i;
     }
 }